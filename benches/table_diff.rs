@@ -0,0 +1,191 @@
+//! Measures the throughput of comparing the data of a table through chunk hashes.
+//!
+//! The executors below stand in for the databases, answering every query after a simulated
+//! latency: a fixed round trip plus a cost per hashed row. The numbers are therefore reproducible
+//! and reflect the overhead of the chunk loop along with how the chunk size trades round trips
+//! against the size of each hash query.
+//!
+//! Run with `cargo bench --bench table_diff`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rust_pgdatadiff::diff::diff_payload::DiffPayload;
+use rust_pgdatadiff::diff::table::query::input::{
+    QueryAllPrimaryKeysInput, QueryCopyRowsInput, QueryHashDataInput, QueryKeyCursorInput,
+    QueryKeyRangeInput, QueryPrimaryKeysInput, QueryRowEstimatesInput, QueryTableColumnsInput,
+    QueryTableCountInput, QueryTableCountsInput, QueryTableNamesInput,
+};
+use rust_pgdatadiff::diff::table::query::table_query_executor::{
+    TableDualSourceQueryExecutor, TableSingleSourceQueryExecutor,
+};
+use rust_pgdatadiff::diff::table::query::table_types::{
+    TableColumn, TableKeyCursor, TableKeyRange,
+};
+use rust_pgdatadiff::diff::table::table_differ::TableDiffer;
+
+/// Latency of a query that does not depend on the number of rows (e.g. counts or catalog lookups).
+const ROUND_TRIP: Duration = Duration::from_millis(1);
+
+/// Latency added by every row hashed by a hash query.
+const PER_ROW: Duration = Duration::from_nanos(50);
+
+const ROW_COUNTS: [i64; 2] = [100_000, 1_000_000];
+const CHUNK_SIZES: [i64; 3] = [1_000, 10_000, 100_000];
+
+/// Answers the queries of a single table of `rows` rows, identical in both databases.
+struct SimulatedExecutor {
+    rows: i64,
+}
+
+#[async_trait]
+impl TableSingleSourceQueryExecutor for SimulatedExecutor {
+    async fn query_table_names(&self, _input: QueryTableNamesInput) -> Vec<String> {
+        vec!["bench_table".to_string()]
+    }
+
+    async fn query_primary_keys(&self, _input: QueryPrimaryKeysInput) -> Result<Vec<String>> {
+        tokio::time::sleep(ROUND_TRIP).await;
+        Ok(vec!["id".to_string()])
+    }
+
+    async fn query_all_primary_keys(
+        &self,
+        input: QueryAllPrimaryKeysInput,
+    ) -> HashMap<String, Vec<String>> {
+        tokio::time::sleep(ROUND_TRIP).await;
+        input
+            .table_names()
+            .iter()
+            .map(|table_name| (table_name.to_string(), vec!["id".to_string()]))
+            .collect()
+    }
+
+    async fn query_unique_keys(&self, _input: QueryPrimaryKeysInput) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn query_view_names(&self, _input: QueryTableNamesInput) -> Vec<String> {
+        Vec::new()
+    }
+
+    async fn query_row_estimates(&self, _input: QueryRowEstimatesInput) -> HashMap<String, i64> {
+        HashMap::from([("bench_table".to_string(), self.rows)])
+    }
+}
+
+#[async_trait]
+impl TableDualSourceQueryExecutor for SimulatedExecutor {
+    async fn query_table_count(&self, _input: QueryTableCountInput) -> (Result<i64>, Result<i64>) {
+        tokio::time::sleep(ROUND_TRIP).await;
+        (Ok(self.rows), Ok(self.rows))
+    }
+
+    async fn query_table_counts(
+        &self,
+        input: QueryTableCountsInput,
+    ) -> (Result<HashMap<String, i64>>, Result<HashMap<String, i64>>) {
+        tokio::time::sleep(ROUND_TRIP).await;
+        let counts = input
+            .table_names()
+            .iter()
+            .map(|(table_name, _)| (table_name.name().to_string(), self.rows))
+            .collect::<HashMap<String, i64>>();
+        (Ok(counts.clone()), Ok(counts))
+    }
+
+    async fn query_second_table_names(&self, _input: QueryTableNamesInput) -> Result<Vec<String>> {
+        Ok(vec!["bench_table".to_string()])
+    }
+
+    async fn query_hash_data(&self, input: QueryHashDataInput) -> (Result<String>, Result<String>) {
+        let window_rows = input
+            .offset()
+            .offset()
+            .min(self.rows - input.position().position())
+            .max(0);
+        tokio::time::sleep(ROUND_TRIP + PER_ROW * window_rows as u32).await;
+        (Ok("hash".to_string()), Ok("hash".to_string()))
+    }
+
+    async fn query_table_columns(
+        &self,
+        _input: QueryTableColumnsInput,
+    ) -> (Result<Vec<TableColumn>>, Result<Vec<TableColumn>>) {
+        tokio::time::sleep(ROUND_TRIP).await;
+        (Ok(Vec::new()), Ok(Vec::new()))
+    }
+
+    async fn query_key_range(
+        &self,
+        _input: QueryKeyRangeInput,
+    ) -> (Result<TableKeyRange>, Result<TableKeyRange>) {
+        (
+            Err(anyhow!("Key ranges are not simulated")),
+            Err(anyhow!("Key ranges are not simulated")),
+        )
+    }
+
+    async fn query_key_cursor(&self, input: QueryKeyCursorInput) -> Result<Option<TableKeyCursor>> {
+        tokio::time::sleep(ROUND_TRIP).await;
+        let key = input.offset().offset().to_string();
+        Ok(Some(TableKeyCursor::new(format!("'{}'", key))))
+    }
+
+    async fn query_copy_diff(&self, _input: QueryCopyRowsInput) -> Result<Option<i64>> {
+        Ok(None)
+    }
+}
+
+fn bench_payload(chunk_size: i64) -> DiffPayload {
+    DiffPayload::builder()
+        .first_db("first_db")
+        .second_db("second_db")
+        .only_tables(true)
+        .only_sequences(false)
+        .only_count(false)
+        .chunk_size(chunk_size)
+        .start_position(0)
+        .max_connections(10)
+        .include_tables(Vec::<String>::new())
+        .exclude_tables(Vec::<String>::new())
+        .schema_name("public")
+        .accept_invalid_certs_first_db(false)
+        .accept_invalid_certs_second_db(false)
+        .build()
+}
+
+fn bench_diff_table_data(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("diff_table_data");
+    group.sample_size(10);
+
+    for rows in ROW_COUNTS {
+        group.throughput(Throughput::Elements(rows as u64));
+
+        for chunk_size in CHUNK_SIZES {
+            let table_differ =
+                TableDiffer::new(SimulatedExecutor { rows }, SimulatedExecutor { rows });
+            let diff_payload = bench_payload(chunk_size);
+
+            group.bench_with_input(
+                BenchmarkId::new(format!("{} rows", rows), chunk_size),
+                &chunk_size,
+                |b, _| {
+                    b.to_async(&runtime).iter(|| {
+                        table_differ.diff_all_table_data(&diff_payload, "public".to_string())
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_diff_table_data);
+criterion_main!(benches);
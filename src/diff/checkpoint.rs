@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{LineWriter, Write};
+use std::sync::{Mutex, PoisonError};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::diff::table::query::output::TableDiffOutput;
+
+/// A verdict of a table, as recorded on its own line of a checkpoint file.
+#[derive(Debug, Serialize, Deserialize)]
+struct CheckpointRecord {
+    schema: String,
+    table: String,
+    verdict: String,
+}
+
+/// The tables compared by previous runs of an interrupted diff, recorded to a checkpoint file
+/// as each one completes, so that a resumed run skips them.
+///
+/// Every verdict is appended as a line of JSON, so that a run killed mid-way loses at most the
+/// line being written, which is ignored when the checkpoint is loaded. Tables whose comparison
+/// timed out or failed are not recorded, so that they are compared again.
+pub struct Checkpoint {
+    completed: HashSet<(String, String)>,
+    writer: Mutex<LineWriter<File>>,
+}
+
+impl Checkpoint {
+    /// Loads the tables recorded to the given checkpoint file, creating it if missing,
+    /// and opens it to append the verdicts of the tables compared from now on.
+    pub fn open(checkpoint_file: &str) -> Result<Self> {
+        let open_error = |e: std::io::Error| {
+            anyhow!("Failed to open checkpoint file {}: {}", checkpoint_file, e)
+        };
+
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(checkpoint_file)
+            .map_err(open_error)?;
+        let contents = std::fs::read_to_string(checkpoint_file).map_err(open_error)?;
+
+        // End the line left partially written by a killed run, so that the next verdict
+        // starts a line of its own rather than completing the malformed one
+        let mut writer = LineWriter::new(file);
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            writeln!(writer).map_err(open_error)?;
+        }
+
+        Ok(Self {
+            completed: completed_tables(&contents),
+            writer: Mutex::new(writer),
+        })
+    }
+
+    /// Determines whether the table of the schema was compared by a previous run.
+    pub fn is_completed(&self, schema_name: &str, table_name: &str) -> bool {
+        self.completed
+            .contains(&(schema_name.to_string(), table_name.to_string()))
+    }
+
+    /// Returns the number of tables compared by previous runs.
+    pub fn completed_count(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// Appends the verdict of a table to the checkpoint file, unless its comparison timed out or failed.
+    pub fn record(&self, schema_name: &str, table_diff: &TableDiffOutput) -> Result<()> {
+        if matches!(
+            table_diff.relation_output(),
+            TableDiffOutput::Timeout(_, _) | TableDiffOutput::Error(_, _)
+        ) {
+            return Ok(());
+        }
+
+        let record = CheckpointRecord {
+            schema: schema_name.to_string(),
+            table: table_diff.table_name().to_string(),
+            verdict: table_diff.message().trim_end().to_string(),
+        };
+        let line = serde_json::to_string(&record)
+            .map_err(|e| anyhow!("Failed to serialize checkpoint: {}", e))?;
+
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        writeln!(writer, "{}", line).map_err(|e| anyhow!("Failed to write checkpoint: {}", e))
+    }
+}
+
+/// Returns the schema and name of every table recorded to the contents of a checkpoint file,
+/// skipping the malformed lines (e.g. the last one of a run killed while writing it).
+fn completed_tables(contents: &str) -> HashSet<(String, String)> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            serde_json::from_str::<CheckpointRecord>(line)
+                .inspect_err(|e| warn!("Ignoring a malformed checkpoint line: {}", e))
+                .ok()
+        })
+        .map(|record| (record.schema, record.table))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::table::query::output::{TableCountDiff, TableSource};
+
+    fn checkpoint_file(name: &str) -> String {
+        let checkpoint_file = std::env::temp_dir().join(name);
+        _ = std::fs::remove_file(&checkpoint_file);
+        checkpoint_file.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_completed_tables_skip_malformed_lines() {
+        let contents =
+            "{\"schema\":\"public\",\"table\":\"orders\",\"verdict\":\"orders - No diff\"}\n\
+                        \n\
+                        {\"schema\":\"public\",\"table\":\"custo";
+
+        assert_eq!(
+            completed_tables(contents),
+            HashSet::from([("public".to_string(), "orders".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_resumed_checkpoint_holds_recorded_tables() {
+        let checkpoint_file = checkpoint_file("rust-pgdatadiff-test-resumed-checkpoint.jsonl");
+
+        let checkpoint = Checkpoint::open(&checkpoint_file).unwrap();
+        assert_eq!(checkpoint.completed_count(), 0);
+        checkpoint
+            .record(
+                "public",
+                &TableDiffOutput::Diff("orders".to_string(), TableCountDiff::new(2, 1)),
+            )
+            .unwrap();
+        checkpoint
+            .record(
+                "public",
+                &TableDiffOutput::NotExists("customers".to_string(), TableSource::Second(None)),
+            )
+            .unwrap();
+        checkpoint
+            .record(
+                "public",
+                &TableDiffOutput::Error("payments".to_string(), "connection reset".to_string()),
+            )
+            .unwrap();
+        drop(checkpoint);
+
+        let checkpoint = Checkpoint::open(&checkpoint_file).unwrap();
+
+        assert_eq!(checkpoint.completed_count(), 2);
+        assert!(checkpoint.is_completed("public", "orders"));
+        assert!(checkpoint.is_completed("public", "customers"));
+        // Failed comparisons are retried
+        assert!(!checkpoint.is_completed("public", "payments"));
+        assert!(!checkpoint.is_completed("reporting", "orders"));
+    }
+
+    #[test]
+    fn test_resumed_checkpoint_records_after_a_partial_line() {
+        let checkpoint_file = checkpoint_file("rust-pgdatadiff-test-partial-checkpoint.jsonl");
+        std::fs::write(
+            &checkpoint_file,
+            "{\"schema\":\"public\",\"table\":\"orders\",\"verdict\":\"orders - No diff\"}\n\
+             {\"schema\":\"public\",\"table\":\"custo",
+        )
+        .unwrap();
+
+        let checkpoint = Checkpoint::open(&checkpoint_file).unwrap();
+        assert_eq!(checkpoint.completed_count(), 1);
+        checkpoint
+            .record(
+                "public",
+                &TableDiffOutput::Diff("customers".to_string(), TableCountDiff::new(2, 1)),
+            )
+            .unwrap();
+        drop(checkpoint);
+
+        let checkpoint = Checkpoint::open(&checkpoint_file).unwrap();
+
+        assert_eq!(checkpoint.completed_count(), 2);
+        assert!(checkpoint.is_completed("public", "orders"));
+        assert!(checkpoint.is_completed("public", "customers"));
+    }
+}
@@ -2,6 +2,10 @@ use deadpool_postgres::Pool;
 
 /// This structure is holding 2 Postgres DB pools.
 /// These will be used to query both the source and the destination databases.
+///
+/// Every executor (tables, sequences and column definitions) receives a clone of these pools.
+/// Clones share the same connections, so a single pool exists per database and the configured
+/// `max_connections`, `application_name` and TLS settings apply to all queries alike.
 pub struct DBClients {
     first_db_pool: Pool,
     second_db_pool: Pool,
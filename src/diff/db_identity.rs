@@ -0,0 +1,126 @@
+use anyhow::Result;
+use deadpool_postgres::Pool;
+
+use crate::diff::db_query;
+
+/// Identifies the server instance and the database of a connection.
+const DATABASE_IDENTITY_QUERY: &str = r#"
+    SELECT system_identifier,
+           current_database() AS database_name,
+           pg_is_in_recovery() AS in_recovery,
+           pg_postmaster_start_time()::text AS started_at
+    FROM pg_control_system()
+    "#;
+
+/// Represents the identity of a database: the server instance it lives on and its name.
+///
+/// Physical replicas share the system identifier of their primary, so the instance is told apart
+/// by whether it is in recovery and by when its server was started as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseIdentity {
+    system_identifier: i64,
+    database_name: String,
+    in_recovery: bool,
+    started_at: String,
+}
+
+impl DatabaseIdentity {
+    pub fn new(
+        system_identifier: i64,
+        database_name: impl Into<String>,
+        in_recovery: bool,
+        started_at: impl Into<String>,
+    ) -> Self {
+        Self {
+            system_identifier,
+            database_name: database_name.into(),
+            in_recovery,
+            started_at: started_at.into(),
+        }
+    }
+
+    /// Queries the identity of the database behind the given pool.
+    pub async fn query(db_pool: &Pool) -> Result<Self> {
+        let client = db_pool.get().await?;
+        let row = db_query::query_one(&client, DATABASE_IDENTITY_QUERY).await?;
+
+        Ok(Self::new(
+            row.try_get("system_identifier")?,
+            row.try_get::<_, String>("database_name")?,
+            row.try_get("in_recovery")?,
+            row.try_get::<_, String>("started_at")?,
+        ))
+    }
+
+    pub fn system_identifier(&self) -> i64 {
+        self.system_identifier
+    }
+
+    pub fn database_name(&self) -> &str {
+        &self.database_name
+    }
+
+    /// Determines whether both identities refer to the same database of the same server instance.
+    ///
+    /// A physical replica is not the same database as its primary, nor as another replica.
+    pub fn is_same_as(&self, other: &DatabaseIdentity) -> bool {
+        self == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STARTED_AT: &str = "2024-05-01 09:30:00.123456+00";
+
+    #[test]
+    fn test_same_instance_and_database() {
+        let first = DatabaseIdentity::new(7234567890123456789, "example", false, STARTED_AT);
+        let second = DatabaseIdentity::new(7234567890123456789, "example", false, STARTED_AT);
+
+        assert!(first.is_same_as(&second));
+    }
+
+    #[test]
+    fn test_same_instance_different_database() {
+        let first = DatabaseIdentity::new(7234567890123456789, "example", false, STARTED_AT);
+        let second = DatabaseIdentity::new(7234567890123456789, "example_copy", false, STARTED_AT);
+
+        assert!(!first.is_same_as(&second));
+    }
+
+    #[test]
+    fn test_different_instance_same_database() {
+        let first = DatabaseIdentity::new(7234567890123456789, "example", false, STARTED_AT);
+        let second = DatabaseIdentity::new(7111111111111111111, "example", false, STARTED_AT);
+
+        assert!(!first.is_same_as(&second));
+    }
+
+    #[test]
+    fn test_primary_and_replica_of_same_database() {
+        let primary = DatabaseIdentity::new(7234567890123456789, "example", false, STARTED_AT);
+        let replica = DatabaseIdentity::new(
+            7234567890123456789,
+            "example",
+            true,
+            "2024-05-02 11:00:00.654321+00",
+        );
+
+        assert!(!primary.is_same_as(&replica));
+    }
+
+    #[test]
+    fn test_replicas_of_same_primary() {
+        let first = DatabaseIdentity::new(7234567890123456789, "example", true, STARTED_AT);
+        let second = DatabaseIdentity::new(
+            7234567890123456789,
+            "example",
+            true,
+            "2024-05-02 11:00:00.654321+00",
+        );
+
+        assert!(!first.is_same_as(&second));
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use deadpool::managed::{Manager, Object, Pool};
+use tokio::time::Instant;
+use tracing::warn;
+
+/// The longest time to wait for a connection of a pool, before the query is reported as failed.
+pub(crate) const POOL_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The delay before retrying to open a connection, doubled after every failed attempt.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// The longest delay between two attempts to open a connection.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Takes a connection from the pool, waiting for one to free up while all of them are in use
+/// (e.g. under high concurrency with a small `max_connections`).
+///
+/// Failures to open a new connection (e.g. the server momentarily refusing more connections)
+/// are retried with an exponential backoff. An error is only returned once no connection
+/// could be obtained within `max_wait`.
+pub(crate) async fn get_client<M: Manager>(pool: &Pool<M>, max_wait: Duration) -> Result<Object<M>>
+where
+    M::Error: std::fmt::Display,
+{
+    let deadline = Instant::now() + max_wait;
+    let mut retry_delay = INITIAL_RETRY_DELAY;
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let error = match tokio::time::timeout(remaining, pool.get()).await {
+            Ok(Ok(client)) => return Ok(client),
+            Ok(Err(e)) => e,
+            Err(_) => {
+                return Err(anyhow!(
+                    "Timed out after {}ms waiting for a free database connection",
+                    max_wait.as_millis()
+                ))
+            }
+        };
+
+        if deadline.saturating_duration_since(Instant::now()) <= retry_delay {
+            return Err(anyhow!(
+                "Failed to get a database connection within {}ms: {}",
+                max_wait.as_millis(),
+                error
+            ));
+        }
+
+        warn!(
+            "Failed to get a database connection, retrying in {}ms: {}",
+            retry_delay.as_millis(),
+            error
+        );
+        tokio::time::sleep(retry_delay).await;
+        retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deadpool::managed::{Metrics, RecycleResult};
+    use deadpool_postgres::tokio_postgres::NoTls;
+    use deadpool_postgres::{Config, PoolConfig, Runtime};
+    use std::convert::Infallible;
+
+    /// Manages connections that always open, so that a pool only runs out of them while all are in use.
+    #[derive(Debug)]
+    struct IdleManager;
+
+    impl Manager for IdleManager {
+        type Type = ();
+        type Error = Infallible;
+
+        async fn create(&self) -> Result<(), Infallible> {
+            Ok(())
+        }
+
+        async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_client_errors_after_a_bounded_wait() {
+        // A single connection to a port nothing listens on, so that every attempt fails
+        let mut cfg = Config::new();
+        cfg.url = Some("postgres://postgres@127.0.0.1:1/example".to_string());
+        cfg.pool = Some(PoolConfig::new(1));
+        let pool = cfg.create_pool(Some(Runtime::Tokio1), NoTls).unwrap();
+
+        let start = Instant::now();
+        let clients = futures::future::join(
+            get_client(&pool, Duration::from_millis(500)),
+            get_client(&pool, Duration::from_millis(500)),
+        )
+        .await;
+
+        assert!(clients.0.is_err());
+        assert!(clients.1.is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_get_client_waits_for_a_connection_in_use() {
+        let pool = Pool::builder(IdleManager).max_size(1).build().unwrap();
+        let held_client = get_client(&pool, Duration::from_millis(500)).await.unwrap();
+
+        // Errors once the only connection is held for longer than the wait
+        let start = Instant::now();
+        let error = get_client(&pool, Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Timed out after 200ms"));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        // Succeeds once the connection is released within the wait
+        let release = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            drop(held_client);
+        };
+        let (client, _) =
+            futures::future::join(get_client(&pool, Duration::from_secs(5)), release).await;
+        assert!(client.is_ok());
+    }
+}
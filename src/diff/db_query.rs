@@ -0,0 +1,29 @@
+use anyhow::{anyhow, Result};
+use deadpool_postgres::tokio_postgres::{Client, Error, Row};
+
+/// Runs a query as an unnamed statement, parsed and executed in a single round trip.
+///
+/// Unlike a prepared statement, an unnamed statement does not outlive its query, so that queries
+/// also run through a connection pooler in transaction pooling mode (e.g. PgBouncer), which may
+/// hand every transaction of a connection to a different server.
+pub(crate) async fn query(client: &Client, query: &str) -> Result<Vec<Row>, Error> {
+    client.query_typed(query, &[]).await
+}
+
+/// Runs a query returning at most one row as an unnamed statement, as [`query`] does.
+pub(crate) async fn query_opt(client: &Client, query: &str) -> Result<Option<Row>, Error> {
+    Ok(self::query(client, query).await?.into_iter().next())
+}
+
+/// Runs a query returning exactly one row (e.g. an aggregate) as an unnamed statement, as [`query`] does.
+///
+/// A failure of the database is returned as the [`Error`] it raised, for
+/// [`crate::diff::types::QueryTimeoutError::map_error`] to tell timeouts apart.
+pub(crate) async fn query_one(client: &Client, query: &str) -> Result<Row> {
+    let mut rows = self::query(client, query).await?;
+
+    match rows.len() {
+        1 => Ok(rows.remove(0)),
+        row_count => Err(anyhow!("Expected a single row, got {}", row_count)),
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use deadpool_postgres::tokio_postgres::types::Type;
+use deadpool_postgres::Pool;
+
+use crate::diff::db_query;
+
+/// Captures the latest WAL position a replica received from its primary, or NULL on a primary.
+const RECEIVED_LSN_QUERY: &str = "SELECT pg_last_wal_receive_lsn()::text AS received_lsn";
+
+/// Measures how far the replay of a replica is behind the given WAL position, along with
+/// how long ago the last replayed transaction was committed on the primary.
+const REPLICA_LAG_QUERY: &str = r#"
+    SELECT GREATEST(pg_wal_lsn_diff($1::text::pg_lsn, pg_last_wal_replay_lsn()), 0)::bigint AS lag_bytes,
+           EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp())::float8 AS replay_delay_secs
+    "#;
+
+/// The interval between two measurements of the lag, while waiting for a replica to catch up.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Represents how far the replay of a replica lags behind the WAL received from its primary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaLag {
+    bytes: i64,
+    replay_delay_secs: Option<f64>,
+}
+
+impl ReplicaLag {
+    pub fn new(bytes: i64, replay_delay_secs: Option<f64>) -> Self {
+        Self {
+            bytes,
+            replay_delay_secs,
+        }
+    }
+
+    /// Waits until the replica behind the given pool replays the WAL it had received from its
+    /// primary when called, for at most the given duration, and returns its lag at that point.
+    ///
+    /// Returns `None` when the database is not a replica, since it has nothing to catch up with.
+    pub async fn await_catch_up(db_pool: &Pool, timeout: Duration) -> Result<Option<Self>> {
+        let client = db_pool.get().await?;
+        let row = db_query::query_one(&client, RECEIVED_LSN_QUERY).await?;
+        let Some(received_lsn) = row.try_get::<_, Option<String>>("received_lsn")? else {
+            return Ok(None);
+        };
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let row = client
+                .query_typed(REPLICA_LAG_QUERY, &[(&received_lsn, Type::TEXT)])
+                .await?
+                .into_iter()
+                .next()
+                .context("The replica lag query returned no row")?;
+            let lag = Self::new(row.try_get("lag_bytes")?, row.try_get("replay_delay_secs")?);
+
+            if lag.is_caught_up() || tokio::time::Instant::now() >= deadline {
+                return Ok(Some(lag));
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    pub fn bytes(&self) -> i64 {
+        self.bytes
+    }
+
+    pub fn replay_delay_secs(&self) -> Option<f64> {
+        self.replay_delay_secs
+    }
+
+    /// Determines whether the replica has replayed all the WAL it was measured against.
+    pub fn is_caught_up(&self) -> bool {
+        self.bytes == 0
+    }
+}
+
+impl Display for ReplicaLag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} bytes of WAL behind", self.bytes)?;
+        match self.replay_delay_secs {
+            Some(secs) => write!(f, ", last replayed transaction committed {:.1}s ago", secs),
+            None => write!(f, ", no transaction replayed yet"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replica_lag_is_caught_up() {
+        assert!(ReplicaLag::new(0, Some(0.5)).is_caught_up());
+        assert!(!ReplicaLag::new(8192, Some(0.5)).is_caught_up());
+    }
+
+    #[test]
+    fn test_replica_lag_display() {
+        assert_eq!(
+            ReplicaLag::new(8192, Some(2.0)).to_string(),
+            "8192 bytes of WAL behind, last replayed transaction committed 2.0s ago"
+        );
+        assert_eq!(
+            ReplicaLag::new(0, None).to_string(),
+            "0 bytes of WAL behind, no transaction replayed yet"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use anyhow::Result;
+use deadpool_postgres::tokio_postgres::Client;
+use deadpool_postgres::{ClientWrapper, Object, Pool};
+
+use crate::diff::db_query;
+
+/// Begins the transaction exporting the snapshot, so that it sees a single state of the database.
+const EXPORT_SNAPSHOT_TRANSACTION: &str = "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY";
+
+/// Exports the snapshot of the current transaction for other transactions to import.
+const EXPORT_SNAPSHOT_QUERY: &str = "SELECT pg_export_snapshot() AS snapshot_id";
+
+/// Represents a snapshot exported by a transaction of a database.
+///
+/// The exporting transaction is held open on a connection detached from its pool, for as long as
+/// a clone of the snapshot is alive. Dropping the last clone closes the connection, which ends
+/// the transaction.
+#[derive(Clone)]
+pub struct DbSnapshot {
+    id: String,
+    _holder: Arc<ClientWrapper>,
+}
+
+impl DbSnapshot {
+    /// Exports a snapshot of the database behind the given pool.
+    pub async fn export(db_pool: &Pool) -> Result<Self> {
+        let client = db_pool.get().await?;
+        client.batch_execute(EXPORT_SNAPSHOT_TRANSACTION).await?;
+        let row = db_query::query_one(&client, EXPORT_SNAPSHOT_QUERY).await?;
+
+        Ok(Self {
+            id: row.try_get("snapshot_id")?,
+            _holder: Arc::new(Object::take(client)),
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// A pooled client whose queries see the database as of a snapshot, if any.
+///
+/// The client runs in a read-only transaction importing the snapshot until
+/// [`SnapshotClient::end`] rolls it back. When dropped before that (e.g. because the query was
+/// cancelled), the connection is detached from its pool and closed, which aborts the transaction,
+/// instead of going back to the pool in the middle of it.
+pub struct SnapshotClient {
+    client: Option<Object>,
+    in_transaction: bool,
+}
+
+impl SnapshotClient {
+    /// Begins a transaction on the given client, which sees the database as of the given snapshot.
+    /// Without a snapshot, the client runs its queries outside of any transaction.
+    pub async fn begin(client: Object, snapshot: Option<&DbSnapshot>) -> Result<Self> {
+        let Some(snapshot) = snapshot else {
+            return Ok(Self {
+                client: Some(client),
+                in_transaction: false,
+            });
+        };
+
+        // Guard the transaction as soon as it may have begun
+        let snapshot_client = Self {
+            client: Some(client),
+            in_transaction: true,
+        };
+        snapshot_client
+            .batch_execute(&import_statement(snapshot.id()))
+            .await?;
+
+        Ok(snapshot_client)
+    }
+
+    /// Ends the transaction, if any, even if one of its queries failed, and returns the client to its pool.
+    pub async fn end(mut self) {
+        if self.in_transaction && self.batch_execute("ROLLBACK").await.is_ok() {
+            self.in_transaction = false;
+        }
+    }
+}
+
+impl Deref for SnapshotClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client.as_ref().unwrap()
+    }
+}
+
+impl Drop for SnapshotClient {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if self.in_transaction {
+                drop(Object::take(client));
+            }
+        }
+    }
+}
+
+/// Builds the statements beginning a transaction that imports the snapshot with the given id.
+fn import_statement(snapshot_id: &str) -> String {
+    format!(
+        "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY; SET TRANSACTION SNAPSHOT '{}'",
+        snapshot_id.replace('\'', "''")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_statement() {
+        assert_eq!(
+            import_statement("00000003-0000001B-1"),
+            "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY; SET TRANSACTION SNAPSHOT '00000003-0000001B-1'"
+        );
+    }
+}
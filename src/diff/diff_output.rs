@@ -1,10 +1,209 @@
+use crate::diff::run_metadata::RunMetadata;
+use crate::diff::schema::output::ColumnDiffOutput;
 use crate::diff::sequence::query::output::SequenceDiffOutput;
+use crate::diff::summary::DiffSummary;
 use crate::diff::table::query::output::TableDiffOutput;
+use crate::diff::trigger::output::TriggerDiffOutput;
+use crate::diff::types::SchemaName;
 
 /// The output of a diff operation.
 /// This is used in order to have a common format for
-/// both table and sequence diff outputs.
+/// table, sequence, schema and trigger diff outputs.
+/// Each output is tagged with the schema it was produced for.
+#[derive(Clone)]
 pub enum DiffOutput {
-    TableDiff(TableDiffOutput),
-    SequenceDiff(SequenceDiffOutput),
+    TableDiff(SchemaName, TableDiffOutput),
+    SequenceDiff(SchemaName, SequenceDiffOutput),
+    SchemaDiff(SchemaName, ColumnDiffOutput),
+    TriggerDiff(SchemaName, TriggerDiffOutput),
+}
+
+impl DiffOutput {
+    /// Returns the schema this output was produced for.
+    pub fn schema_name(&self) -> &SchemaName {
+        match self {
+            Self::TableDiff(schema_name, _) => schema_name,
+            Self::SequenceDiff(schema_name, _) => schema_name,
+            Self::SchemaDiff(schema_name, _) => schema_name,
+            Self::TriggerDiff(schema_name, _) => schema_name,
+        }
+    }
+
+    /// Determines whether the table, sequence, columns or trigger this output refers to differ
+    /// between the databases.
+    pub fn is_difference(&self) -> bool {
+        match self {
+            Self::TableDiff(_, table_diff) => table_diff.is_difference(),
+            Self::SequenceDiff(_, sequence_diff) => sequence_diff.is_difference(),
+            Self::SchemaDiff(_, column_diff) => column_diff.is_difference(),
+            Self::TriggerDiff(_, trigger_diff) => trigger_diff.is_difference(),
+        }
+    }
+
+    /// Returns the schema-qualified name of the table, sequence or trigger this output refers to
+    /// (e.g. `public.orders`, or `public.orders.audit` for a trigger).
+    pub fn qualified_name(&self) -> String {
+        let schema_name = self.schema_name().name();
+        match self {
+            Self::TableDiff(_, table_diff) => {
+                format!("{}.{}", schema_name, table_diff.table_name())
+            }
+            Self::SequenceDiff(_, sequence_diff) => {
+                format!("{}.{}", schema_name, sequence_diff.sequence_name())
+            }
+            Self::SchemaDiff(_, column_diff) => {
+                format!("{}.{}", schema_name, column_diff.table_name())
+            }
+            Self::TriggerDiff(_, trigger_diff) => format!(
+                "{}.{}.{}",
+                schema_name,
+                trigger_diff.table_name(),
+                trigger_diff.trigger_name()
+            ),
+        }
+    }
+}
+
+/// The result of a diff run: the output of every table, sequence and schema comparison,
+/// along with their summary.
+pub struct DiffResult {
+    outputs: Vec<DiffOutput>,
+    summary: DiffSummary,
+    cancelled: bool,
+    timed_out: bool,
+    run_metadata: Option<RunMetadata>,
+    report: Option<String>,
+}
+
+impl DiffResult {
+    pub fn new(outputs: Vec<DiffOutput>, summary: DiffSummary) -> Self {
+        Self {
+            outputs,
+            summary,
+            cancelled: false,
+            timed_out: false,
+            run_metadata: None,
+            report: None,
+        }
+    }
+
+    /// Marks the result as cancelled, holding only the outputs gathered before the diff was cancelled.
+    pub fn with_cancelled(mut self, cancelled: bool) -> Self {
+        self.cancelled = cancelled;
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Marks the result as cancelled once the maximum runtime of the diff was exceeded.
+    pub fn with_timed_out(mut self, timed_out: bool) -> Self {
+        self.timed_out = timed_out;
+        self
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Attaches the metadata of the run (e.g. its ID and when it started and finished).
+    pub fn with_run_metadata(mut self, run_metadata: RunMetadata) -> Self {
+        self.run_metadata = Some(run_metadata);
+        self
+    }
+
+    /// Returns the metadata of the run, unless it was a dry run or a watch.
+    pub fn run_metadata(&self) -> Option<&RunMetadata> {
+        self.run_metadata.as_ref()
+    }
+
+    /// Attaches the rendered report, or the recorded queries of a dry run, left to the caller to print.
+    pub fn with_report(mut self, report: Option<String>) -> Self {
+        self.report = report;
+        self
+    }
+
+    /// Returns the rendered report when no output file was given, or the queries of a dry run.
+    pub fn report(&self) -> Option<&str> {
+        self.report.as_deref()
+    }
+
+    pub fn outputs(&self) -> &[DiffOutput] {
+        &self.outputs
+    }
+
+    pub fn summary(&self) -> &DiffSummary {
+        &self.summary
+    }
+
+    /// Determines whether any table, sequence or column definition differs between the databases.
+    pub fn has_differences(&self) -> bool {
+        self.outputs.iter().any(DiffOutput::is_difference)
+    }
+
+    pub fn into_outputs(self) -> Vec<DiffOutput> {
+        self.outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::diff::sequence::query::output::SequenceCountDiff;
+    use crate::diff::table::query::output::{TableSource, TableTimings};
+
+    fn table_diff(table_diff: TableDiffOutput) -> DiffOutput {
+        DiffOutput::TableDiff(SchemaName::new("public"), table_diff)
+    }
+
+    #[test]
+    fn test_is_difference() {
+        assert!(
+            !table_diff(TableDiffOutput::NoCountDiff("table1".to_string(), 10)).is_difference()
+        );
+        assert!(table_diff(TableDiffOutput::NotExists(
+            "table1".to_string(),
+            TableSource::First(None)
+        ))
+        .is_difference());
+        assert!(table_diff(TableDiffOutput::DataDiffWithDuration(
+            "table1".to_string(),
+            0,
+            100,
+            TableTimings::new(Duration::ZERO, Duration::from_millis(1))
+        ))
+        .is_difference());
+        assert!(!table_diff(TableDiffOutput::Timeout(
+            "table1".to_string(),
+            TableSource::First(None)
+        ))
+        .is_difference());
+        assert!(DiffOutput::SequenceDiff(
+            SchemaName::new("public"),
+            SequenceDiffOutput::Diff("sequence1".to_string(), SequenceCountDiff::new(2, 1))
+        )
+        .is_difference());
+        assert!(!DiffOutput::SchemaDiff(
+            SchemaName::new("public"),
+            ColumnDiffOutput::NoDiff("table1".to_string())
+        )
+        .is_difference());
+    }
+
+    #[test]
+    fn test_diff_result_has_differences() {
+        let outputs = vec![
+            table_diff(TableDiffOutput::NoCountDiff("table1".to_string(), 10)),
+            table_diff(TableDiffOutput::NoPrimaryKeyFound("table2".to_string())),
+        ];
+        let summary = DiffSummary::new(&outputs, Duration::from_millis(1));
+        let diff_result = DiffResult::new(outputs, summary);
+
+        assert!(!diff_result.has_differences());
+        assert_eq!(diff_result.summary().tables_identical(), 1);
+        assert_eq!(diff_result.into_outputs().len(), 2);
+    }
 }
@@ -0,0 +1,89 @@
+use std::sync::{Arc, Mutex};
+
+/// Records the SQL the query executors would run, grouped by the object it targets.
+///
+/// In dry-run mode, the executors record every query they render. Catalog lookups
+/// (table and sequence names, keys, columns, sequence definitions) still run, since they
+/// decide which data queries are issued, while data queries (row counts, hashes,
+/// sequence values) are only recorded and never executed.
+#[derive(Clone, Default)]
+pub struct QueryLog(Arc<Mutex<Vec<(String, String)>>>);
+
+impl QueryLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a query under the given group (e.g. `table public.users`).
+    pub fn record(&self, group: impl Into<String>, query: impl Into<String>) {
+        self.0.lock().unwrap().push((group.into(), query.into()));
+    }
+
+    /// Renders the recorded queries, grouped in the order each group was first seen.
+    pub fn render(&self) -> String {
+        let queries = self.0.lock().unwrap();
+
+        let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+        for (group, query) in queries.iter() {
+            let index = groups.iter().position(|(name, _)| *name == group.as_str());
+            match index {
+                Some(index) => groups[index].1.push(query.as_str()),
+                None => groups.push((group.as_str(), vec![query.as_str()])),
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(group, group_queries)| {
+                let group_queries = group_queries
+                    .into_iter()
+                    .map(|query| format!("{};", sanitize_query(query)))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                format!("-- {}\n{}", group, group_queries)
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+}
+
+/// Collapses the whitespace of a rendered query and strips its trailing semicolon.
+fn sanitize_query(query: &str) -> String {
+    query
+        .split_ascii_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .trim_end_matches(';')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_groups_queries_by_object() {
+        let query_log = QueryLog::new();
+        query_log.record("table public.table1", "SELECT count(*) FROM public.table1");
+        query_log.record(
+            "sequence public.sequence1",
+            "SELECT last_value FROM public.sequence1;",
+        );
+        query_log.record(
+            "table public.table1",
+            "\n    SELECT md5(t)\n    FROM public.table1\n",
+        );
+
+        let expected = [
+            "-- table public.table1",
+            "SELECT count(*) FROM public.table1;",
+            "SELECT md5(t) FROM public.table1;",
+            "",
+            "-- sequence public.sequence1",
+            "SELECT last_value FROM public.sequence1;",
+        ]
+        .join("\n");
+
+        assert_eq!(query_log.render(), expected);
+    }
+}
@@ -0,0 +1,112 @@
+use std::future::Future;
+
+use futures::stream;
+use futures::{Stream, StreamExt};
+
+/// Runs the futures concurrently, yielding the output of each, along with the index of its
+/// future, as soon as it completes.
+///
+/// With `max_concurrency`, at most that many futures run at a time, the next one starting as soon
+/// as one completes. Otherwise, all of them run at once.
+///
+/// With `fail_fast`, the stream ends right after the first output that is a difference,
+/// dropping (and so cancelling) the futures still running. The outputs are then partial: they hold
+/// the difference and the outputs of the futures that completed before it.
+pub(crate) fn until_difference<F, T>(
+    futures: impl IntoIterator<Item = F>,
+    max_concurrency: Option<usize>,
+    fail_fast: bool,
+    is_difference: impl Fn(&T) -> bool,
+) -> impl Stream<Item = (usize, T)>
+where
+    F: Future<Output = T>,
+{
+    let pending = stream::iter(
+        futures
+            .into_iter()
+            .enumerate()
+            .map(|(index, future)| async move { (index, future.await) }),
+    )
+    .buffer_unordered(max_concurrency.unwrap_or(usize::MAX));
+
+    stop_after(pending, move |(_, output)| {
+        fail_fast && is_difference(output)
+    })
+}
+
+/// Yields the items of the stream, ending right after the first item to stop at,
+/// without waiting for the next one.
+pub(crate) fn stop_after<S: Stream>(
+    items: S,
+    stop: impl Fn(&S::Item) -> bool,
+) -> impl Stream<Item = S::Item> {
+    stream::unfold(Some((Box::pin(items), stop)), |state| async move {
+        let (mut items, stop) = state?;
+        let item = items.next().await?;
+        let state = (!stop(&item)).then_some((items, stop));
+
+        Some((item, state))
+    })
+}
+
+/// Collects the outputs yielded by [`until_difference`] back in the order of their futures.
+pub(crate) async fn collect_in_order<T>(outputs: impl Stream<Item = (usize, T)>) -> Vec<T> {
+    let mut outputs = outputs.collect::<Vec<_>>().await;
+
+    outputs.sort_by_key(|(index, _)| *index);
+    outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    async fn delayed(value: i32, millis: u64) -> i32 {
+        tokio::time::sleep(Duration::from_millis(millis)).await;
+        value
+    }
+
+    #[tokio::test]
+    async fn test_join_keeps_the_order_of_the_futures() {
+        let futures = vec![delayed(1, 30), delayed(2, 10), delayed(3, 20)];
+
+        let outputs =
+            collect_in_order(until_difference(futures, None, false, |value| *value == 2)).await;
+
+        assert_eq!(outputs, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_join_stops_at_the_first_difference() {
+        let futures = vec![delayed(1, 10), delayed(2, 20), delayed(3, 1000)];
+
+        let outputs =
+            collect_in_order(until_difference(futures, None, true, |value| *value == 2)).await;
+
+        assert_eq!(outputs, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_until_difference_yields_outputs_as_they_complete() {
+        let futures = vec![delayed(1, 30), delayed(2, 10), delayed(3, 20)];
+
+        let outputs = until_difference(futures, None, false, |value| *value == 2)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(outputs, vec![(1, 2), (2, 3), (0, 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_until_difference_runs_at_most_max_concurrency_futures_at_a_time() {
+        let futures = vec![delayed(1, 40), delayed(2, 20), delayed(3, 30)];
+
+        // The third future only starts once the second completes, so finishes after the first
+        let outputs = until_difference(futures, Some(2), false, |value| *value == 2)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(outputs, vec![(1, 2), (0, 1), (2, 3)]);
+    }
+}
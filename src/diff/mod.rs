@@ -1,9 +1,27 @@
+pub mod checkpoint;
 pub(crate) mod db_clients;
+pub mod db_identity;
+pub(crate) mod db_pool;
+pub(crate) mod db_query;
+pub mod db_replica;
+pub mod db_snapshot;
 pub mod diff_ops;
 pub mod diff_output;
 pub mod diff_payload;
+pub mod dry_run;
+pub(crate) mod fail_fast;
 #[cfg(test)]
 mod internal;
+pub mod password_file;
+pub mod pg_service;
+pub mod progress;
+pub mod report;
+pub mod repro;
+pub mod run_metadata;
+pub mod schema;
 pub mod sequence;
+pub mod summary;
 pub mod table;
+pub mod tls;
+pub mod trigger;
 pub mod types;
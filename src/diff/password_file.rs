@@ -0,0 +1,84 @@
+use anyhow::{anyhow, Result};
+
+/// Reads the password of a database from the given file, so that it can be left out of the
+/// connection URL (and so out of the shell history and the process list).
+///
+/// The password is the first line of the file. As for `.pgpass`, the file must not be readable
+/// by the group or by others on Unix.
+pub fn read_password(password_file: &str) -> Result<String> {
+    let read_error =
+        |e: std::io::Error| anyhow!("Failed to read password file {}: {}", password_file, e);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = std::fs::metadata(password_file)
+            .map_err(read_error)?
+            .permissions()
+            .mode();
+        if mode & 0o077 != 0 {
+            return Err(anyhow!(
+                "Password file {} has group or world access (mode {:o}), it should be 0600 or less",
+                password_file,
+                mode & 0o777
+            ));
+        }
+    }
+
+    let contents = std::fs::read_to_string(password_file).map_err(read_error)?;
+
+    password(&contents).ok_or_else(|| anyhow!("Password file {} is empty", password_file))
+}
+
+/// Returns the password held by the contents of a password file, without its line ending.
+fn password(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .next()
+        .filter(|password| !password.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password() {
+        assert_eq!(Some("secret".to_string()), password("secret\n"));
+        assert_eq!(Some("secret".to_string()), password("secret\r\nignored\n"));
+        assert_eq!(None, password(""));
+        assert_eq!(None, password("\n"));
+    }
+
+    #[test]
+    fn test_missing_password_file() {
+        let error = read_password("/nonexistent/password").err().unwrap();
+
+        assert!(error
+            .to_string()
+            .starts_with("Failed to read password file /nonexistent/password"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_password_file_readable_by_others() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let password_file = std::env::temp_dir().join("rust-pgdatadiff-test-password");
+        std::fs::write(&password_file, "secret\n").unwrap();
+
+        std::fs::set_permissions(&password_file, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let error = read_password(password_file.to_str().unwrap())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("has group or world access"));
+
+        std::fs::set_permissions(&password_file, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let password = read_password(password_file.to_str().unwrap()).unwrap();
+        assert_eq!("secret", password);
+
+        std::fs::remove_file(&password_file).unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+use std::io::Write;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+/// Returns the `MultiProgress` through which every progress bar is drawn.
+fn multi_progress() -> &'static MultiProgress {
+    static MULTI_PROGRESS: OnceLock<MultiProgress> = OnceLock::new();
+    MULTI_PROGRESS.get_or_init(MultiProgress::new)
+}
+
+/// Creates the overall progress bar, counting the tables completed out of `total`.
+///
+/// A hidden bar is returned when progress is disabled, so callers can update it unconditionally.
+pub(crate) fn tables_bar(enabled: bool, total: usize) -> ProgressBar {
+    if !enabled {
+        return ProgressBar::hidden();
+    }
+
+    let style = ProgressStyle::with_template("{prefix} [{bar:40}] {pos}/{len} tables ({elapsed})")
+        .unwrap()
+        .progress_chars("=> ");
+
+    multi_progress().add(
+        ProgressBar::new(total as u64)
+            .with_style(style)
+            .with_prefix("Tables"),
+    )
+}
+
+/// Creates a spinner reporting the rows hashed so far for a single table.
+///
+/// A hidden spinner is returned when progress is disabled.
+pub(crate) fn table_spinner(enabled: bool, table_name: &str) -> ProgressBar {
+    if !enabled {
+        return ProgressBar::hidden();
+    }
+
+    let style = ProgressStyle::with_template("{spinner} {prefix}: {msg}").unwrap();
+
+    let spinner = multi_progress().add(
+        ProgressBar::new_spinner()
+            .with_style(style)
+            .with_prefix(table_name.to_string()),
+    );
+    spinner.enable_steady_tick(Duration::from_millis(100));
+    spinner
+}
+
+/// A writer that suspends the progress bars while writing to stderr.
+///
+/// Use it as the writer of the tracing subscriber (e.g. `.with_writer(ProgressWriter::new)`)
+/// so that log lines are printed above the bars instead of interleaving with them,
+/// while stdout is left to the rendered results (e.g. an NDJSON stream).
+#[derive(Default)]
+pub struct ProgressWriter;
+
+impl ProgressWriter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Write for ProgressWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        multi_progress().suspend(|| std::io::stderr().write(buf))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        multi_progress().suspend(|| std::io::stderr().flush())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_progress_is_hidden() {
+        assert!(tables_bar(false, 10).is_hidden());
+        assert!(table_spinner(false, "table1").is_hidden());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::sequence::query::output::{SequenceDiffOutput, SequenceSource};
+use crate::diff::table::query::output::{TableDiffOutput, TableSource};
+
+const TABLE_HEADER: [&str; 6] = [
+    "schema",
+    "table",
+    "status",
+    "first_count",
+    "second_count",
+    "duration_ms",
+];
+const SEQUENCE_HEADER: [&str; 5] = [
+    "schema",
+    "sequence",
+    "status",
+    "first_value",
+    "second_value",
+];
+
+/// Renders the table and sequence results as CSV.
+///
+/// Table results come first, under the `schema,table,status,first_count,second_count,duration_ms`
+/// header, followed by a blank line and the sequence results, under the
+/// `schema,sequence,status,first_value,second_value` header. A section without results is omitted,
+/// as are the column definition results. Values that are unknown for a result are left empty.
+pub fn render(diff_output: &[DiffOutput]) -> String {
+    let table_rows = diff_output
+        .iter()
+        .filter_map(|diff_output| match diff_output {
+            DiffOutput::TableDiff(schema_name, table_diff) => {
+                Some(table_row(schema_name.name(), table_diff))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let sequence_rows = diff_output
+        .iter()
+        .filter_map(|diff_output| match diff_output {
+            DiffOutput::SequenceDiff(schema_name, sequence_diff) => {
+                Some(sequence_row(schema_name.name(), sequence_diff))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let sections = [
+        section(&TABLE_HEADER, &table_rows),
+        section(&SEQUENCE_HEADER, &sequence_rows),
+    ];
+
+    sections
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn section(header: &[&str], rows: &[Vec<String>]) -> Option<String> {
+    if rows.is_empty() {
+        return None;
+    }
+
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+    writer.write_record(header).unwrap();
+    for row in rows {
+        writer.write_record(row).unwrap();
+    }
+
+    Some(String::from_utf8(writer.into_inner().unwrap()).unwrap())
+}
+
+fn table_row(schema_name: &str, table_diff: &TableDiffOutput) -> Vec<String> {
+    let (status, first_count, second_count, duration_ms): (&str, _, _, _) = match table_diff
+        .relation_output()
+    {
+        TableDiffOutput::NoCountDiff(_, count) => ("no-diff", Some(*count), Some(*count), None),
+        TableDiffOutput::NoDiffWithDuration(_, timings) => {
+            ("no-diff", None, None, Some(timings.total().as_millis()))
+        }
+        TableDiffOutput::Diff(_, count_diff) => (
+            "count-diff",
+            Some(count_diff.first()),
+            Some(count_diff.second()),
+            None,
+        ),
+        TableDiffOutput::EmptyOnOneSide(_, TableSource::First(_), rows) => {
+            ("empty-first", Some(0), Some(*rows), None)
+        }
+        TableDiffOutput::EmptyOnOneSide(_, TableSource::Second(_), rows) => {
+            ("empty-second", Some(*rows), Some(0), None)
+        }
+        TableDiffOutput::NotExists(_, TableSource::First(_)) => ("missing-first", None, None, None),
+        TableDiffOutput::NotExists(_, TableSource::Second(_)) => {
+            ("missing-second", None, None, None)
+        }
+        TableDiffOutput::Timeout(_, TableSource::First(_)) => ("timeout-first", None, None, None),
+        TableDiffOutput::Timeout(_, TableSource::Second(_)) => ("timeout-second", None, None, None),
+        TableDiffOutput::Error(_, _) => ("error", None, None, None),
+        TableDiffOutput::NoPrimaryKeyFound(_) => ("no-pk", None, None, None),
+        TableDiffOutput::DataDiffWithDuration(_, _, _, timings) => {
+            ("data-diff", None, None, Some(timings.total().as_millis()))
+        }
+        TableDiffOutput::EstimatedCount(_, first, second) if first == second => {
+            ("estimated-no-diff", Some(*first), Some(*second), None)
+        }
+        TableDiffOutput::EstimatedCount(_, first, second) => {
+            ("estimated-count-diff", Some(*first), Some(*second), None)
+        }
+        TableDiffOutput::View(_) | TableDiffOutput::Sampled(_, _) => {
+            unreachable!("Views and samples are unwrapped")
+        }
+    };
+
+    vec![
+        schema_name.to_string(),
+        table_diff.table_name().to_string(),
+        status.to_string(),
+        cell(first_count),
+        cell(second_count),
+        cell(duration_ms),
+    ]
+}
+
+fn sequence_row(schema_name: &str, sequence_diff: &SequenceDiffOutput) -> Vec<String> {
+    let (status, first_value, second_value): (&str, _, _) = match sequence_diff {
+        SequenceDiffOutput::NoDiff(_) => ("no-diff", None, None),
+        SequenceDiffOutput::Diff(_, count_diff) => (
+            "value-diff",
+            Some(count_diff.first()),
+            Some(count_diff.second()),
+        ),
+        SequenceDiffOutput::StructureDiff(_, _) => ("structure-diff", None, None),
+        SequenceDiffOutput::OwnershipDiff(_, _) => ("ownership-diff", None, None),
+        SequenceDiffOutput::NotExists(_, SequenceSource::First(_)) => ("missing-first", None, None),
+        SequenceDiffOutput::NotExists(_, SequenceSource::Second(_)) => {
+            ("missing-second", None, None)
+        }
+        SequenceDiffOutput::Timeout(_, SequenceSource::First(_)) => ("timeout-first", None, None),
+        SequenceDiffOutput::Timeout(_, SequenceSource::Second(_)) => ("timeout-second", None, None),
+    };
+
+    vec![
+        schema_name.to_string(),
+        sequence_diff.sequence_name().to_string(),
+        status.to_string(),
+        cell(first_value),
+        cell(second_value),
+    ]
+}
+
+/// Formats an optional value, leaving it empty when unknown.
+fn cell(value: Option<impl ToString>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::diff::schema::output::ColumnDiffOutput;
+    use crate::diff::sequence::query::output::SequenceCountDiff;
+    use crate::diff::table::query::output::{TableCountDiff, TableTimings};
+    use crate::diff::types::SchemaName;
+
+    fn table_diff(table_diff: TableDiffOutput) -> DiffOutput {
+        DiffOutput::TableDiff(SchemaName::new("public"), table_diff)
+    }
+
+    fn sequence_diff(sequence_diff: SequenceDiffOutput) -> DiffOutput {
+        DiffOutput::SequenceDiff(SchemaName::new("public"), sequence_diff)
+    }
+
+    #[test]
+    fn test_render_tables_and_sequences() {
+        let diff_output = vec![
+            table_diff(TableDiffOutput::NoCountDiff("table1".to_string(), 10)),
+            table_diff(TableDiffOutput::Diff(
+                "table2".to_string(),
+                TableCountDiff::new(2, 1),
+            )),
+            table_diff(TableDiffOutput::EmptyOnOneSide(
+                "table3".to_string(),
+                TableSource::Second(None),
+                5,
+            )),
+            table_diff(TableDiffOutput::DataDiffWithDuration(
+                "table4".to_string(),
+                0,
+                100,
+                TableTimings::new(Duration::ZERO, Duration::from_millis(15)),
+            )),
+            table_diff(TableDiffOutput::NotExists(
+                "table5".to_string(),
+                TableSource::First(None),
+            )),
+            table_diff(TableDiffOutput::NoPrimaryKeyFound("table6".to_string())),
+            sequence_diff(SequenceDiffOutput::NoDiff("sequence1".to_string())),
+            sequence_diff(SequenceDiffOutput::Diff(
+                "sequence2".to_string(),
+                SequenceCountDiff::new(7, 3),
+            )),
+            DiffOutput::SchemaDiff(
+                SchemaName::new("public"),
+                ColumnDiffOutput::NoDiff("table1".to_string()),
+            ),
+        ];
+
+        assert_eq!(
+            render(&diff_output),
+            "schema,table,status,first_count,second_count,duration_ms\n\
+             public,table1,no-diff,10,10,\n\
+             public,table2,count-diff,2,1,\n\
+             public,table3,empty-second,5,0,\n\
+             public,table4,data-diff,,,15\n\
+             public,table5,missing-first,,,\n\
+             public,table6,no-pk,,,\n\
+             \n\
+             schema,sequence,status,first_value,second_value\n\
+             public,sequence1,no-diff,,\n\
+             public,sequence2,value-diff,7,3\n"
+        );
+    }
+
+    #[test]
+    fn test_render_omits_sections_without_results() {
+        let diff_output = vec![sequence_diff(SequenceDiffOutput::NotExists(
+            "sequence1".to_string(),
+            SequenceSource::Second(None),
+        ))];
+
+        assert_eq!(
+            render(&diff_output),
+            "schema,sequence,status,first_value,second_value\n\
+             public,sequence1,missing-second,,\n"
+        );
+        assert_eq!(render(&[]), "");
+    }
+}
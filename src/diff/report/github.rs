@@ -0,0 +1,211 @@
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::schema::output::ColumnDiffOutput;
+use crate::diff::sequence::query::output::SequenceDiffOutput;
+use crate::diff::table::query::output::TableDiffOutput;
+use crate::diff::trigger::output::TriggerDiffOutput;
+
+const ERROR: &str = "error";
+const WARNING: &str = "warning";
+
+/// Renders the diff results as GitHub Actions workflow commands.
+///
+/// Every differing or missing object is emitted as an `::error` annotation, while objects
+/// that could not be compared are emitted as `::warning` annotations.
+/// Objects without differences are omitted.
+pub fn render(diff_output: &[DiffOutput]) -> String {
+    diff_output
+        .iter()
+        .filter_map(annotation)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn annotation(diff_output: &DiffOutput) -> Option<String> {
+    let schema_name = diff_output.schema_name().name();
+
+    let (level, title, message): (&str, String, String) = match diff_output {
+        DiffOutput::TableDiff(_, table_diff) => {
+            let level = match table_diff.relation_output() {
+                TableDiffOutput::NoCountDiff(_, _) | TableDiffOutput::NoDiffWithDuration(_, _) => {
+                    return None
+                }
+                TableDiffOutput::EstimatedCount(_, first, second) if first == second => {
+                    return None
+                }
+                TableDiffOutput::NoPrimaryKeyFound(_) => WARNING,
+                TableDiffOutput::NotExists(_, _)
+                | TableDiffOutput::Diff(_, _)
+                | TableDiffOutput::EmptyOnOneSide(_, _, _)
+                | TableDiffOutput::Timeout(_, _)
+                | TableDiffOutput::Error(_, _)
+                | TableDiffOutput::DataDiffWithDuration(_, _, _, _)
+                | TableDiffOutput::EstimatedCount(_, _, _) => ERROR,
+                TableDiffOutput::View(_) | TableDiffOutput::Sampled(_, _) => {
+                    unreachable!("Views and samples are unwrapped")
+                }
+            };
+            let object = if table_diff.is_view() {
+                "View"
+            } else {
+                "Table"
+            };
+            let title = format!("{} {}.{}", object, schema_name, table_diff.table_name());
+            (level, title, table_diff.message())
+        }
+        DiffOutput::SequenceDiff(_, sequence_diff) => {
+            let level = match sequence_diff {
+                SequenceDiffOutput::NoDiff(_) => return None,
+                SequenceDiffOutput::NotExists(_, _)
+                | SequenceDiffOutput::Diff(_, _)
+                | SequenceDiffOutput::Timeout(_, _)
+                | SequenceDiffOutput::StructureDiff(_, _)
+                | SequenceDiffOutput::OwnershipDiff(_, _) => ERROR,
+            };
+            let title = format!("Sequence {}.{}", schema_name, sequence_diff.sequence_name());
+            (level, title, sequence_diff.message())
+        }
+        DiffOutput::SchemaDiff(_, column_diff) => {
+            let level = match column_diff {
+                ColumnDiffOutput::NoDiff(_) => return None,
+                ColumnDiffOutput::NotExists(_, _)
+                | ColumnDiffOutput::Added(_, _)
+                | ColumnDiffOutput::Removed(_, _)
+                | ColumnDiffOutput::DefinitionChanged(_, _) => ERROR,
+            };
+            let title = format!("Columns {}.{}", schema_name, column_diff.table_name());
+            (level, title, column_diff.message())
+        }
+        DiffOutput::TriggerDiff(_, trigger_diff) => {
+            let level = match trigger_diff {
+                TriggerDiffOutput::NoDiff(_, _) => return None,
+                TriggerDiffOutput::NotExists(_, _, _)
+                | TriggerDiffOutput::DefinitionChanged(_, _, _, _) => ERROR,
+            };
+            let title = format!(
+                "Trigger {}.{}.{}",
+                schema_name,
+                trigger_diff.table_name(),
+                trigger_diff.trigger_name()
+            );
+            (level, title, trigger_diff.message())
+        }
+    };
+
+    Some(format!(
+        "::{} title={}::{}",
+        level,
+        escape_property(&title),
+        escape_data(message.trim_end())
+    ))
+}
+
+/// Escapes the message of a workflow command.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::sequence::query::output::{SequenceCountDiff, SequenceSource};
+    use crate::diff::table::query::output::{TableCountDiff, TableSource, TableTimings};
+    use crate::diff::types::SchemaName;
+    use std::time::Duration;
+
+    fn table_diff(table_diff: TableDiffOutput) -> DiffOutput {
+        DiffOutput::TableDiff(SchemaName::new("public"), table_diff)
+    }
+
+    #[test]
+    fn test_render_omits_objects_without_differences() {
+        let diff_output = vec![
+            table_diff(TableDiffOutput::NoCountDiff("table1".to_string(), 10)),
+            table_diff(TableDiffOutput::NoDiffWithDuration(
+                "table2".to_string(),
+                TableTimings::new(Duration::ZERO, Duration::from_millis(1)),
+            )),
+            DiffOutput::SequenceDiff(
+                SchemaName::new("public"),
+                SequenceDiffOutput::NoDiff("sequence1".to_string()),
+            ),
+            DiffOutput::SchemaDiff(
+                SchemaName::new("public"),
+                ColumnDiffOutput::NoDiff("table1".to_string()),
+            ),
+        ];
+
+        assert_eq!(render(&diff_output), "");
+    }
+
+    #[test]
+    fn test_render_table_annotations() {
+        let diff_output = vec![
+            table_diff(TableDiffOutput::NotExists(
+                "table1".to_string(),
+                TableSource::Second(None),
+            )),
+            table_diff(TableDiffOutput::Diff(
+                "table2".to_string(),
+                TableCountDiff::new(2, 1),
+            )),
+            table_diff(TableDiffOutput::NoPrimaryKeyFound("table3".to_string())),
+            table_diff(TableDiffOutput::DataDiffWithDuration(
+                "table4".to_string(),
+                0,
+                100,
+                TableTimings::new(Duration::ZERO, Duration::from_millis(5)),
+            )),
+        ];
+
+        let expected = [
+            "::error title=Table public.table1::table1 - Does not exist in second",
+            "::error title=Table public.table2::table2 - First table rows: 2, Second table rows: 1, off by 1 (50.00%25)",
+            "::warning title=Table public.table3::table3 - No primary key found",
+            "::error title=Table public.table4::table4 - Data diff between rows [0,100] - in 5ms (counts: 0ms, hashes: 5ms)",
+        ]
+        .join("\n");
+
+        assert_eq!(render(&diff_output), expected);
+    }
+
+    #[test]
+    fn test_render_sequence_and_column_annotations() {
+        let diff_output = vec![
+            DiffOutput::SequenceDiff(
+                SchemaName::new("public"),
+                SequenceDiffOutput::Diff("sequence1".to_string(), SequenceCountDiff::new(2, 1)),
+            ),
+            DiffOutput::SequenceDiff(
+                SchemaName::new("public"),
+                SequenceDiffOutput::NotExists("sequence2".to_string(), SequenceSource::First(None)),
+            ),
+            DiffOutput::SchemaDiff(
+                SchemaName::new("public"),
+                ColumnDiffOutput::Added("table1".to_string(), "notes".to_string()),
+            ),
+        ];
+
+        let expected = [
+            "::error title=Sequence public.sequence1::Difference in sequence:sequence1 - First: 2, Second: 1",
+            "::error title=Sequence public.sequence2::sequence2 - Does not exist in first",
+            "::error title=Columns public.table1::table1.notes - Column only exists in second",
+        ]
+        .join("\n");
+
+        assert_eq!(render(&diff_output), expected);
+    }
+
+    #[test]
+    fn test_escape_workflow_command_values() {
+        assert_eq!(escape_data("50%\nnext"), "50%25%0Anext");
+        assert_eq!(escape_property("a:b,c"), "a%3Ab%2Cc");
+    }
+}
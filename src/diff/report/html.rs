@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::report::{result, Outcome, Row};
+
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 2em; }
+details { margin-bottom: 1em; }
+summary { font-size: 1.2em; font-weight: bold; cursor: pointer; }
+table { border-collapse: collapse; margin-top: 0.5em; width: 100%; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }
+th { background: #f0f0f0; cursor: pointer; }
+"#;
+
+const SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable th").forEach(function (th) {
+  th.addEventListener("click", function () {
+    var table = th.closest("table");
+    var index = Array.prototype.indexOf.call(th.parentNode.children, th);
+    var ascending = th.dataset.order !== "asc";
+    th.dataset.order = ascending ? "asc" : "desc";
+    var rows = Array.prototype.slice.call(table.tBodies[0].rows);
+    rows.sort(function (a, b) {
+      var result = a.cells[index].textContent.localeCompare(b.cells[index].textContent);
+      return ascending ? result : -result;
+    });
+    rows.forEach(function (row) { table.tBodies[0].appendChild(row); });
+  });
+});
+"#;
+
+/// Renders the diff results as a self-contained HTML page.
+///
+/// Results are grouped into collapsible Differing, Missing, Errors and Matched sections,
+/// each titled with its number of results and holding a table that is sorted by clicking
+/// on its headers. Sections other than Matched are expanded by default.
+/// The tags of the run, if any, are listed below the title.
+pub fn render(diff_output: &[DiffOutput], tags: &BTreeMap<String, String>) -> String {
+    let results = diff_output.iter().map(result).collect::<Vec<_>>();
+
+    let sections = Outcome::ALL
+        .iter()
+        .map(|outcome| {
+            let rows = results
+                .iter()
+                .filter(|(row_outcome, _)| row_outcome == outcome)
+                .map(|(_, row)| row)
+                .collect::<Vec<_>>();
+            section(*outcome, &rows)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let tags = if tags.is_empty() {
+        String::new()
+    } else {
+        let tags = tags
+            .iter()
+            .map(|(key, value)| format!("<li>{}={}</li>", escape(key), escape(value)))
+            .collect::<Vec<String>>()
+            .join("");
+        format!("<ul class=\"tags\">{}</ul>\n", tags)
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Diff report</title>\n<style>{}</style>\n</head>\n<body>\n<h1>Diff report</h1>\n{}{}\n<script>{}</script>\n</body>\n</html>",
+        STYLE, tags, sections, SCRIPT
+    )
+}
+
+fn section(outcome: Outcome, rows: &[&Row<'_>]) -> String {
+    let open = if outcome == Outcome::Matched {
+        ""
+    } else {
+        " open"
+    };
+
+    let rows = rows
+        .iter()
+        .map(|row| {
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(row.schema),
+                escape(row.object),
+                escape(row.name),
+                escape(&row.message)
+            )
+        })
+        .collect::<Vec<String>>();
+
+    format!(
+        "<details{} id=\"{}\">\n<summary>{} ({})</summary>\n<table class=\"sortable\">\n<thead><tr><th>Schema</th><th>Object</th><th>Name</th><th>Message</th></tr></thead>\n<tbody>\n{}\n</tbody>\n</table>\n</details>",
+        open,
+        outcome.title().to_lowercase(),
+        outcome.title(),
+        rows.len(),
+        rows.join("\n")
+    )
+}
+
+/// Escapes the characters with a special meaning in HTML text.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::schema::output::ColumnDiffOutput;
+    use crate::diff::sequence::query::output::{SequenceCountDiff, SequenceDiffOutput};
+    use crate::diff::table::query::output::{TableCountDiff, TableDiffOutput, TableSource};
+    use crate::diff::types::SchemaName;
+
+    fn table_diff(table_diff: TableDiffOutput) -> DiffOutput {
+        DiffOutput::TableDiff(SchemaName::new("public"), table_diff)
+    }
+
+    #[test]
+    fn test_render_groups_results_by_outcome() {
+        let diff_output = vec![
+            table_diff(TableDiffOutput::NoCountDiff("table1".to_string(), 10)),
+            table_diff(TableDiffOutput::Diff(
+                "table2".to_string(),
+                TableCountDiff::new(2, 1),
+            )),
+            table_diff(TableDiffOutput::NotExists(
+                "table3".to_string(),
+                TableSource::Second(None),
+            )),
+            table_diff(TableDiffOutput::NoPrimaryKeyFound("table4".to_string())),
+            DiffOutput::SequenceDiff(
+                SchemaName::new("public"),
+                SequenceDiffOutput::Diff("sequence1".to_string(), SequenceCountDiff::new(2, 1)),
+            ),
+            DiffOutput::SchemaDiff(
+                SchemaName::new("public"),
+                ColumnDiffOutput::NoDiff("table1".to_string()),
+            ),
+        ];
+
+        let html = render(&diff_output, &BTreeMap::new());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<details open id=\"differing\">\n<summary>Differing (2)</summary>"));
+        assert!(html.contains("<details open id=\"missing\">\n<summary>Missing (1)</summary>"));
+        assert!(html.contains("<details open id=\"errors\">\n<summary>Errors (1)</summary>"));
+        assert!(html.contains("<details id=\"matched\">\n<summary>Matched (2)</summary>"));
+        assert_eq!(html.matches("<tr><td>").count(), diff_output.len());
+        assert!(html.contains(
+            "<tr><td>public</td><td>table</td><td>table3</td><td>table3 - Does not exist in second</td></tr>"
+        ));
+    }
+
+    #[test]
+    fn test_render_without_results_keeps_empty_sections() {
+        let html = render(&[], &BTreeMap::new());
+
+        for title in ["Differing", "Missing", "Errors", "Matched"] {
+            assert!(html.contains(&format!("<summary>{} (0)</summary>", title)));
+        }
+        assert_eq!(html.matches("<tr><td>").count(), 0);
+    }
+
+    #[test]
+    fn test_escape_html_text() {
+        assert_eq!(
+            escape("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_render_lists_tags() {
+        let tags = BTreeMap::from([("env".to_string(), "prod".to_string())]);
+
+        let html = render(&[], &tags);
+
+        assert!(html.contains("<h1>Diff report</h1>\n<ul class=\"tags\"><li>env=prod</li></ul>\n"));
+        assert!(!render(&[], &BTreeMap::new()).contains("class=\"tags\""));
+    }
+}
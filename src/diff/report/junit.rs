@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::{anyhow, Result};
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::report::{result, Outcome};
+use crate::diff::summary::DiffSummary;
+
+/// The name of the test suite holding the results.
+const SUITE_NAME: &str = "rust-pgdatadiff";
+
+/// Renders the diff results as a JUnit XML report, for CI dashboards that parse test results.
+///
+/// Every table, sequence, column definition and trigger is a test case named after its
+/// schema-qualified name and classified by its schema and object (e.g. `public.table`).
+/// A case passes when it matches, fails when it differs or is missing, and errors when
+/// it could not be compared, the plain message of the result describing the failure.
+/// The tags of the run, if any, are listed as properties of the suite.
+pub fn render(
+    diff_output: &[DiffOutput],
+    summary: &DiffSummary,
+    tags: &BTreeMap<String, String>,
+) -> String {
+    let results = diff_output
+        .iter()
+        .map(|diff_output| (diff_output.qualified_name(), result(diff_output)))
+        .collect::<Vec<_>>();
+
+    let count = |outcomes: &[Outcome]| {
+        results
+            .iter()
+            .filter(|(_, (outcome, _))| outcomes.contains(outcome))
+            .count()
+    };
+    let failures = count(&[Outcome::Differing, Outcome::Missing]);
+    let errors = count(&[Outcome::Errors]);
+
+    let test_cases = results
+        .iter()
+        .map(|(name, (outcome, row))| {
+            let class_name = escape(&format!("{}.{}", row.schema, row.object));
+            let name = escape(name);
+            let message = escape(&row.message);
+            match outcome {
+                Outcome::Matched => format!(
+                    "    <testcase classname=\"{}\" name=\"{}\"/>\n",
+                    class_name, name
+                ),
+                Outcome::Differing | Outcome::Missing => format!(
+                    "    <testcase classname=\"{}\" name=\"{}\">\n      <failure message=\"{}\" type=\"{}\">{}</failure>\n    </testcase>\n",
+                    class_name,
+                    name,
+                    message,
+                    outcome.title().to_lowercase(),
+                    message
+                ),
+                Outcome::Errors => format!(
+                    "    <testcase classname=\"{}\" name=\"{}\">\n      <error message=\"{}\">{}</error>\n    </testcase>\n",
+                    class_name, name, message, message
+                ),
+            }
+        })
+        .collect::<String>();
+
+    let properties = if tags.is_empty() {
+        String::new()
+    } else {
+        let properties = tags
+            .iter()
+            .fold(String::new(), |mut properties, (key, value)| {
+                let _ = writeln!(
+                    properties,
+                    "      <property name=\"{}\" value=\"{}\"/>",
+                    escape(key),
+                    escape(value)
+                );
+                properties
+            });
+        format!("    <properties>\n{}    </properties>\n", properties)
+    };
+
+    let suite = format!(
+        "name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\"",
+        SUITE_NAME,
+        results.len(),
+        failures,
+        errors,
+        summary.duration_ms() as f64 / 1000.0
+    );
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites {}>\n  <testsuite {}>\n{}{}  </testsuite>\n</testsuites>",
+        suite, suite, properties, test_cases
+    )
+}
+
+/// Writes the diff results as a JUnit XML report to the given file.
+pub fn write(
+    junit_report: &str,
+    diff_output: &[DiffOutput],
+    summary: &DiffSummary,
+    tags: &BTreeMap<String, String>,
+) -> Result<()> {
+    let report = render(diff_output, summary, tags);
+
+    std::fs::write(junit_report, report + "\n")
+        .map_err(|e| anyhow!("Failed to write JUnit report {}: {}", junit_report, e))
+}
+
+/// Escapes the characters with a special meaning in XML attributes and text, and leaves out
+/// the control characters XML does not allow.
+fn escape(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::sequence::query::output::SequenceDiffOutput;
+    use crate::diff::table::query::output::{TableCountDiff, TableDiffOutput, TableSource};
+    use crate::diff::types::SchemaName;
+
+    fn table_diff(table_diff: TableDiffOutput) -> DiffOutput {
+        DiffOutput::TableDiff(SchemaName::new("public"), table_diff)
+    }
+
+    #[test]
+    fn test_render_classifies_test_cases() {
+        let diff_output = vec![
+            table_diff(TableDiffOutput::NoCountDiff("table1".to_string(), 10)),
+            table_diff(TableDiffOutput::Diff(
+                "table2".to_string(),
+                TableCountDiff::new(2, 1),
+            )),
+            table_diff(TableDiffOutput::NotExists(
+                "table3".to_string(),
+                TableSource::Second(None),
+            )),
+            table_diff(TableDiffOutput::Error(
+                "table4".to_string(),
+                "connection reset".to_string(),
+            )),
+            DiffOutput::SequenceDiff(
+                SchemaName::new("public"),
+                SequenceDiffOutput::NoDiff("sequence1".to_string()),
+            ),
+        ];
+
+        let junit = render(&diff_output, &DiffSummary::default(), &BTreeMap::new());
+
+        assert!(junit.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites "));
+        assert!(junit.contains(
+            "<testsuite name=\"rust-pgdatadiff\" tests=\"5\" failures=\"2\" errors=\"1\" time=\"0.000\">"
+        ));
+        assert!(junit.contains("<testcase classname=\"public.table\" name=\"public.table1\"/>"));
+        assert!(
+            junit.contains("<testcase classname=\"public.sequence\" name=\"public.sequence1\"/>")
+        );
+        assert!(junit.contains(
+            "<testcase classname=\"public.table\" name=\"public.table3\">\n      <failure message=\"table3 - Does not exist in second\" type=\"missing\">"
+        ));
+        assert_eq!(junit.matches("<failure ").count(), 2);
+        assert_eq!(junit.matches("<error ").count(), 1);
+        assert!(!junit.contains("<properties>"));
+        assert!(junit.ends_with("  </testsuite>\n</testsuites>"));
+    }
+
+    #[test]
+    fn test_render_lists_tags_as_properties() {
+        let tags = BTreeMap::from([("env".to_string(), "prod".to_string())]);
+
+        let junit = render(&[], &DiffSummary::default(), &tags);
+
+        assert!(junit.contains(
+            "    <properties>\n      <property name=\"env\" value=\"prod\"/>\n    </properties>\n"
+        ));
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape("<a b='c'>\"&\u{1b}[31m</a>"),
+            "&lt;a b=&apos;c&apos;&gt;&quot;&amp;[31m&lt;/a&gt;"
+        );
+    }
+}
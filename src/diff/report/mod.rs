@@ -0,0 +1,349 @@
+pub mod csv;
+pub mod github;
+pub mod html;
+pub mod jsonl;
+pub mod junit;
+pub mod text;
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use anyhow::{anyhow, Result};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::schema::output::ColumnDiffOutput;
+use crate::diff::sequence::query::output::SequenceDiffOutput;
+use crate::diff::summary::DiffSummary;
+use crate::diff::table::query::output::TableDiffOutput;
+use crate::diff::trigger::output::TriggerDiffOutput;
+
+/// The format used to render the results of a diff.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Results are only emitted through the logs.
+    #[default]
+    Text,
+    /// Results are emitted as GitHub Actions workflow commands (annotations).
+    Github,
+    /// Results are emitted as newline delimited JSON, one result per line, as soon as each is known.
+    Jsonl,
+    /// Results are emitted as a self-contained HTML page, grouped by outcome.
+    Html,
+    /// Table and sequence results are emitted as CSV, one row per result.
+    Csv,
+}
+
+/// Renders the diff results in the given format.
+///
+/// Returns `None` for [`OutputFormat::Text`], since its results are already emitted through the logs.
+/// The tags of the run are embedded in the structured formats (JSONL and HTML).
+pub fn render(
+    output_format: OutputFormat,
+    diff_output: &[DiffOutput],
+    summary: &DiffSummary,
+    tags: &BTreeMap<String, String>,
+) -> Option<String> {
+    match output_format {
+        OutputFormat::Text => None,
+        OutputFormat::Github => Some(github::render(diff_output)),
+        OutputFormat::Jsonl => Some(jsonl::render(diff_output, summary, tags, None)),
+        OutputFormat::Html => Some(html::render(diff_output, tags)),
+        OutputFormat::Csv => Some(csv::render(diff_output)),
+    }
+}
+
+/// Writes the diff results, rendered in the given format, to the given file. When no file is
+/// given, the rendered report is returned instead, for the caller to print to the standard output.
+///
+/// Results in the text format are only written to a file, since they are already
+/// emitted through the logs otherwise.
+///
+/// With `only_differences`, only the results that differ are written, and the text format
+/// lists their names alone, one per line, even to the standard output.
+/// Otherwise, with `group_by_status`, the text format groups the results into sections by status,
+/// even to the standard output.
+pub fn write(
+    output_format: OutputFormat,
+    output_file: Option<&str>,
+    diff_output: &[DiffOutput],
+    summary: &DiffSummary,
+    tags: &BTreeMap<String, String>,
+    only_differences: bool,
+    group_by_status: bool,
+) -> Result<Option<String>> {
+    let differences;
+    let diff_output = if only_differences {
+        differences = diff_output
+            .iter()
+            .filter(|diff_output| diff_output.is_difference())
+            .cloned()
+            .collect::<Vec<DiffOutput>>();
+        &differences
+    } else {
+        diff_output
+    };
+
+    let report = match (output_format, output_file) {
+        (OutputFormat::Text, _) if only_differences => Some(text::render_names(diff_output)),
+        (OutputFormat::Text, _) if group_by_status => {
+            Some(text::render_grouped(diff_output, summary))
+        }
+        (OutputFormat::Text, Some(_)) => Some(text::render(diff_output, summary)),
+        _ => render(output_format, diff_output, summary, tags),
+    };
+
+    let Some(report) = report else {
+        return Ok(None);
+    };
+
+    let Some(output_file) = output_file else {
+        return Ok(Some(report));
+    };
+
+    let file = File::create(output_file)
+        .map_err(|e| anyhow!("Failed to create output file {}: {}", output_file, e))?;
+    let mut writer = BufWriter::new(file);
+
+    writeln!(writer, "{}", report)
+        .and_then(|_| writer.flush())
+        .map_err(|e| anyhow!("Failed to write output file {}: {}", output_file, e))?;
+
+    Ok(None)
+}
+
+/// The outcome of a result, under which it is grouped or classified in the reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Matched,
+    Differing,
+    Missing,
+    Errors,
+}
+
+impl Outcome {
+    /// The order in which the sections of the HTML report are rendered.
+    pub(crate) const ALL: [Outcome; 4] = [
+        Outcome::Differing,
+        Outcome::Missing,
+        Outcome::Errors,
+        Outcome::Matched,
+    ];
+
+    pub(crate) fn title(&self) -> &'static str {
+        match self {
+            Outcome::Matched => "Matched",
+            Outcome::Differing => "Differing",
+            Outcome::Missing => "Missing",
+            Outcome::Errors => "Errors",
+        }
+    }
+}
+
+/// A single result of the reports, along with its plain message.
+pub(crate) struct Row<'a> {
+    pub(crate) schema: &'a str,
+    pub(crate) object: &'a str,
+    pub(crate) name: &'a str,
+    pub(crate) message: String,
+}
+
+/// Classifies a result by its outcome.
+pub(crate) fn result(diff_output: &DiffOutput) -> (Outcome, Row<'_>) {
+    let schema_name = diff_output.schema_name().name();
+
+    let (outcome, object, name, message): (Outcome, &str, &str, String) = match diff_output {
+        DiffOutput::TableDiff(_, table_diff) => {
+            let outcome = match table_diff.relation_output() {
+                TableDiffOutput::NoCountDiff(_, _) | TableDiffOutput::NoDiffWithDuration(_, _) => {
+                    Outcome::Matched
+                }
+                TableDiffOutput::EstimatedCount(_, first, second) if first == second => {
+                    Outcome::Matched
+                }
+                TableDiffOutput::NotExists(_, _) => Outcome::Missing,
+                TableDiffOutput::Diff(_, _)
+                | TableDiffOutput::EmptyOnOneSide(_, _, _)
+                | TableDiffOutput::DataDiffWithDuration(_, _, _, _)
+                | TableDiffOutput::EstimatedCount(_, _, _) => Outcome::Differing,
+                TableDiffOutput::Timeout(_, _)
+                | TableDiffOutput::Error(_, _)
+                | TableDiffOutput::NoPrimaryKeyFound(_) => Outcome::Errors,
+                TableDiffOutput::View(_) | TableDiffOutput::Sampled(_, _) => {
+                    unreachable!("Views and samples are unwrapped")
+                }
+            };
+            (
+                outcome,
+                table_diff.object(),
+                table_diff.table_name(),
+                table_diff.message(),
+            )
+        }
+        DiffOutput::SequenceDiff(_, sequence_diff) => {
+            let outcome = match sequence_diff {
+                SequenceDiffOutput::NoDiff(_) => Outcome::Matched,
+                SequenceDiffOutput::NotExists(_, _) => Outcome::Missing,
+                SequenceDiffOutput::Diff(_, _)
+                | SequenceDiffOutput::StructureDiff(_, _)
+                | SequenceDiffOutput::OwnershipDiff(_, _) => Outcome::Differing,
+                SequenceDiffOutput::Timeout(_, _) => Outcome::Errors,
+            };
+            (
+                outcome,
+                "sequence",
+                sequence_diff.sequence_name(),
+                sequence_diff.message(),
+            )
+        }
+        DiffOutput::SchemaDiff(_, column_diff) => {
+            let outcome = match column_diff {
+                ColumnDiffOutput::NoDiff(_) => Outcome::Matched,
+                ColumnDiffOutput::NotExists(_, _) => Outcome::Missing,
+                ColumnDiffOutput::Added(_, _)
+                | ColumnDiffOutput::Removed(_, _)
+                | ColumnDiffOutput::DefinitionChanged(_, _) => Outcome::Differing,
+            };
+            (
+                outcome,
+                "columns",
+                column_diff.table_name(),
+                column_diff.message(),
+            )
+        }
+        DiffOutput::TriggerDiff(_, trigger_diff) => {
+            let outcome = match trigger_diff {
+                TriggerDiffOutput::NoDiff(_, _) => Outcome::Matched,
+                TriggerDiffOutput::NotExists(_, _, _) => Outcome::Missing,
+                TriggerDiffOutput::DefinitionChanged(_, _, _, _) => Outcome::Differing,
+            };
+            (
+                outcome,
+                "trigger",
+                trigger_diff.table_name(),
+                trigger_diff.message(),
+            )
+        }
+    };
+
+    (
+        outcome,
+        Row {
+            schema: schema_name,
+            object,
+            name,
+            message: message.trim_end().to_string(),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::types::SchemaName;
+
+    #[test]
+    fn test_write_to_output_file() {
+        let output_file =
+            std::env::temp_dir().join("rust-pgdatadiff-test-write-to-output-file.jsonl");
+        let output_file = output_file.to_str().unwrap();
+        let diff_output = vec![DiffOutput::TableDiff(
+            SchemaName::new("public"),
+            TableDiffOutput::NoCountDiff("table1".to_string(), 10),
+        )];
+
+        write(
+            OutputFormat::Jsonl,
+            Some(output_file),
+            &diff_output,
+            &DiffSummary::default(),
+            &BTreeMap::new(),
+            false,
+            false,
+        )
+        .unwrap();
+
+        let contents = std::fs::read_to_string(output_file).unwrap();
+        std::fs::remove_file(output_file).unwrap();
+
+        assert_eq!(contents.lines().count(), 2);
+        assert!(contents.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_write_without_output_file_returns_the_report() {
+        let diff_output = vec![DiffOutput::TableDiff(
+            SchemaName::new("public"),
+            TableDiffOutput::NoCountDiff("table1".to_string(), 10),
+        )];
+
+        let report = write(
+            OutputFormat::Csv,
+            None,
+            &diff_output,
+            &DiffSummary::default(),
+            &BTreeMap::new(),
+            false,
+            false,
+        )
+        .unwrap();
+
+        assert!(report.unwrap().contains("table1"));
+    }
+
+    #[test]
+    fn test_write_to_missing_directory() {
+        let error = write(
+            OutputFormat::Text,
+            Some("/nonexistent/rust-pgdatadiff/results.txt"),
+            &[],
+            &DiffSummary::default(),
+            &BTreeMap::new(),
+            false,
+            false,
+        )
+        .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("Failed to create output file /nonexistent/rust-pgdatadiff/results.txt"));
+    }
+
+    #[test]
+    fn test_write_only_differences() {
+        let output_file =
+            std::env::temp_dir().join("rust-pgdatadiff-test-write-only-differences.csv");
+        let output_file = output_file.to_str().unwrap();
+        let diff_output = vec![
+            DiffOutput::TableDiff(
+                SchemaName::new("public"),
+                TableDiffOutput::NoCountDiff("table1".to_string(), 10),
+            ),
+            DiffOutput::TableDiff(
+                SchemaName::new("public"),
+                TableDiffOutput::EstimatedCount("table2".to_string(), 10, 12),
+            ),
+        ];
+
+        write(
+            OutputFormat::Csv,
+            Some(output_file),
+            &diff_output,
+            &DiffSummary::default(),
+            &BTreeMap::new(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let contents = std::fs::read_to_string(output_file).unwrap();
+        std::fs::remove_file(output_file).unwrap();
+
+        assert!(!contents.contains("table1"));
+        assert!(contents.contains("table2"));
+    }
+}
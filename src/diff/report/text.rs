@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+
+use colored::Colorize;
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::report::{result, Outcome};
+use crate::diff::summary::DiffSummary;
+use crate::diff::table::query::output::TableDiffOutput;
+
+/// The status under which a result is listed when the results are grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Status {
+    Identical,
+    CountDiffers,
+    DataDiffers,
+    Missing,
+    NoPrimaryKey,
+    Errors,
+}
+
+impl Status {
+    /// The order in which the sections are rendered.
+    const ALL: [Status; 6] = [
+        Status::Identical,
+        Status::CountDiffers,
+        Status::DataDiffers,
+        Status::Missing,
+        Status::NoPrimaryKey,
+        Status::Errors,
+    ];
+
+    fn title(&self) -> &'static str {
+        match self {
+            Status::Identical => "Identical",
+            Status::CountDiffers => "Count differs",
+            Status::DataDiffers => "Data differs",
+            Status::Missing => "Missing",
+            Status::NoPrimaryKey => "No primary key",
+            Status::Errors => "Errors",
+        }
+    }
+
+    /// Classifies a result by its status, refining its outcome in the reports.
+    ///
+    /// Tables whose counts differ (including tables empty on one side) are told apart from those
+    /// whose data differs, while the other differences (sequences, column definitions and triggers)
+    /// are listed with the data that differs.
+    fn of(diff_output: &DiffOutput) -> Status {
+        let table_diff = match diff_output {
+            DiffOutput::TableDiff(_, table_diff) => Some(table_diff.relation_output()),
+            _ => None,
+        };
+
+        match (result(diff_output).0, table_diff) {
+            (Outcome::Matched, _) => Status::Identical,
+            (
+                Outcome::Differing,
+                Some(
+                    TableDiffOutput::Diff(_, _)
+                    | TableDiffOutput::EmptyOnOneSide(_, _, _)
+                    | TableDiffOutput::EstimatedCount(_, _, _),
+                ),
+            ) => Status::CountDiffers,
+            (Outcome::Differing, _) => Status::DataDiffers,
+            (Outcome::Missing, _) => Status::Missing,
+            (Outcome::Errors, Some(TableDiffOutput::NoPrimaryKeyFound(_))) => Status::NoPrimaryKey,
+            (Outcome::Errors, _) => Status::Errors,
+        }
+    }
+}
+
+/// Groups the results by status, in the order of the sections, each holding the plain message
+/// of its results (prefixed with their schema) in the order of the results.
+/// Statuses without any result are left out.
+pub(crate) fn group_by_status(diff_output: &[DiffOutput]) -> Vec<(Status, Vec<String>)> {
+    Status::ALL
+        .into_iter()
+        .map(|status| {
+            let members = diff_output
+                .iter()
+                .filter(|diff_output| Status::of(diff_output) == status)
+                .map(|diff_output| {
+                    let (_, row) = result(diff_output);
+                    format!("{}: {}", row.schema, row.message)
+                })
+                .collect::<Vec<String>>();
+            (status, members)
+        })
+        .filter(|(_, members)| !members.is_empty())
+        .collect()
+}
+
+/// Renders the diff results as sections grouped by status (e.g. "Count differs"), each listing
+/// its results one per line, followed by the summary.
+///
+/// Only the section titles are colored, which is turned off along with the colors of the logs.
+pub fn render_grouped(diff_output: &[DiffOutput], summary: &DiffSummary) -> String {
+    let summary = summary.to_string();
+
+    group_by_status(diff_output)
+        .into_iter()
+        .map(|(status, members)| {
+            let title = format!("{} ({})", status.title(), members.len());
+            let members = members
+                .iter()
+                .map(|member| format!("  {}", member))
+                .collect::<Vec<String>>()
+                .join("\n");
+            format!("{}\n{}", title.bold(), members)
+        })
+        .chain(std::iter::once(summary.to_string()))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Renders the diff results as plain text, one result per line, followed by the summary.
+///
+/// This is the uncolored counterpart of the results emitted through the logs,
+/// used when they are written to a file.
+pub fn render(diff_output: &[DiffOutput], summary: &DiffSummary) -> String {
+    let summary = summary.to_string();
+    // Dereferencing a `ColoredString` yields its plain, uncolored text
+    let summary: &str = &summary;
+
+    diff_output
+        .iter()
+        .map(|diff_output| {
+            let message = match diff_output {
+                DiffOutput::TableDiff(_, table_diff) => table_diff.message(),
+                DiffOutput::SequenceDiff(_, sequence_diff) => sequence_diff.message(),
+                DiffOutput::SchemaDiff(_, column_diff) => column_diff.message(),
+                DiffOutput::TriggerDiff(_, trigger_diff) => trigger_diff.message(),
+            };
+            format!(
+                "{}: {}",
+                diff_output.schema_name().name(),
+                message.trim_end()
+            )
+        })
+        .chain(std::iter::once(summary.to_string()))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Renders the schema-qualified name of every result that differs, one per line, without any
+/// message or summary, so that the output can be piped into other commands (e.g. `xargs`).
+///
+/// A table whose columns and data both differ is listed once.
+pub fn render_names(diff_output: &[DiffOutput]) -> String {
+    let mut listed = HashSet::new();
+
+    diff_output
+        .iter()
+        .filter(|diff_output| diff_output.is_difference())
+        .map(DiffOutput::qualified_name)
+        .filter(|name| listed.insert(name.clone()))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::diff::sequence::query::output::{SequenceCountDiff, SequenceDiffOutput};
+    use crate::diff::table::query::output::{
+        TableCountDiff, TableDiffOutput, TableSource, TableTimings,
+    };
+    use crate::diff::types::SchemaName;
+
+    #[test]
+    fn test_render_results_and_summary() {
+        let diff_output = vec![DiffOutput::TableDiff(
+            SchemaName::new("public"),
+            TableDiffOutput::NotExists("table1".to_string(), TableSource::Second(None)),
+        )];
+        let summary = DiffSummary::new(&diff_output, Duration::from_millis(5));
+
+        let text = render(&diff_output, &summary);
+
+        assert!(text.starts_with("public: table1 - Does not exist in second\nSummary\n"));
+        assert!(text.ends_with("Total time: 5ms"));
+    }
+
+    #[test]
+    fn test_render_names_of_differences_only() {
+        let diff_output = vec![
+            DiffOutput::TableDiff(
+                SchemaName::new("public"),
+                TableDiffOutput::NoCountDiff("table1".to_string(), 10),
+            ),
+            DiffOutput::TableDiff(
+                SchemaName::new("public"),
+                TableDiffOutput::NotExists("table2".to_string(), TableSource::Second(None)),
+            ),
+            DiffOutput::SequenceDiff(
+                SchemaName::new("reporting"),
+                SequenceDiffOutput::Diff("sequence1".to_string(), SequenceCountDiff::new(2, 1)),
+            ),
+        ];
+
+        assert_eq!(
+            "public.table2\nreporting.sequence1",
+            render_names(&diff_output)
+        );
+    }
+
+    #[test]
+    fn test_group_by_status() {
+        let table_diff = |table_diff| DiffOutput::TableDiff(SchemaName::new("public"), table_diff);
+        let diff_output = vec![
+            table_diff(TableDiffOutput::NotExists(
+                "table1".to_string(),
+                TableSource::Second(None),
+            )),
+            table_diff(TableDiffOutput::NoCountDiff("table2".to_string(), 10)),
+            table_diff(TableDiffOutput::DataDiffWithDuration(
+                "table3".to_string(),
+                0,
+                100,
+                TableTimings::new(Duration::ZERO, Duration::ZERO),
+            )),
+            table_diff(TableDiffOutput::Diff(
+                "table4".to_string(),
+                TableCountDiff::new(2, 1),
+            )),
+            table_diff(TableDiffOutput::NoPrimaryKeyFound("table5".to_string())),
+            table_diff(TableDiffOutput::NoCountDiff("table6".to_string(), 0)),
+            DiffOutput::SequenceDiff(
+                SchemaName::new("reporting"),
+                SequenceDiffOutput::Diff("sequence1".to_string(), SequenceCountDiff::new(2, 1)),
+            ),
+        ];
+
+        let groups = group_by_status(&diff_output);
+
+        let statuses = groups
+            .iter()
+            .map(|(status, members)| (*status, members.len()))
+            .collect::<Vec<(Status, usize)>>();
+        assert_eq!(
+            statuses,
+            vec![
+                (Status::Identical, 2),
+                (Status::CountDiffers, 1),
+                (Status::DataDiffers, 2),
+                (Status::Missing, 1),
+                (Status::NoPrimaryKey, 1),
+            ]
+        );
+        assert_eq!(
+            groups[3].1,
+            vec!["public: table1 - Does not exist in second".to_string()]
+        );
+        assert_eq!(
+            groups[4].1,
+            vec!["public: table5 - No primary key found".to_string()]
+        );
+    }
+}
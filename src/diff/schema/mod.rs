@@ -0,0 +1,5 @@
+pub mod output;
+pub mod schema_differ;
+
+#[cfg(test)]
+mod schema_differ_tests;
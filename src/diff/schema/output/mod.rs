@@ -0,0 +1,197 @@
+use colored::{ColoredString, Colorize};
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::table::query::output::TableSource;
+use crate::diff::table::query::table_types::TableColumn;
+use crate::diff::types::{DiffOutputMarker, SchemaName};
+
+/// Represents the difference in the definition of a column between two tables.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone)]
+pub struct ColumnDefinitionDiff(TableColumn, TableColumn);
+
+impl ColumnDefinitionDiff {
+    /// Creates a new `ColumnDefinitionDiff` instance with the given columns.
+    pub fn new(first: TableColumn, second: TableColumn) -> Self {
+        Self(first, second)
+    }
+
+    pub fn first(&self) -> &TableColumn {
+        &self.0
+    }
+
+    pub fn second(&self) -> &TableColumn {
+        &self.1
+    }
+
+    /// Returns a description of each attribute that differs between the two columns.
+    pub fn changes(&self) -> Vec<String> {
+        let (first, second) = (self.first(), self.second());
+        let mut changes = Vec::new();
+
+        if first.data_type() != second.data_type() {
+            changes.push(format!(
+                "type: {} -> {}",
+                first.data_type(),
+                second.data_type()
+            ));
+        }
+
+        if first.is_nullable() != second.is_nullable() {
+            changes.push(format!(
+                "nullable: {} -> {}",
+                first.is_nullable(),
+                second.is_nullable()
+            ));
+        }
+
+        if first.default() != second.default() {
+            changes.push(format!(
+                "default: {} -> {}",
+                first.default().unwrap_or("none"),
+                second.default().unwrap_or("none")
+            ));
+        }
+
+        if first.comment() != second.comment() {
+            changes.push(format!(
+                "comment: {} -> {}",
+                first.comment().unwrap_or("none"),
+                second.comment().unwrap_or("none")
+            ));
+        }
+
+        changes
+    }
+
+    /// Determines whether the two column definitions differ.
+    pub fn has_changes(&self) -> bool {
+        !self.changes().is_empty()
+    }
+}
+
+/// Represents the output of a column definitions difference.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone)]
+pub enum ColumnDiffOutput {
+    /// Indicates that there is no difference between the columns of the tables.
+    NoDiff(String),
+    /// Indicates that the table does not exist in a specific source.
+    NotExists(String, TableSource),
+    /// Indicates that a column exists only in the second table.
+    Added(String, String),
+    /// Indicates that a column exists only in the first table.
+    Removed(String, String),
+    /// Indicates that a column exists in both tables with a different definition.
+    DefinitionChanged(String, Box<ColumnDefinitionDiff>),
+}
+
+impl ColumnDiffOutput {
+    /// Returns the name of the table this output refers to.
+    pub fn table_name(&self) -> &str {
+        match self {
+            Self::NoDiff(table)
+            | Self::NotExists(table, _)
+            | Self::Added(table, _)
+            | Self::Removed(table, _)
+            | Self::DefinitionChanged(table, _) => table,
+        }
+    }
+
+    /// Determines whether the columns of the table differ between the databases,
+    /// including when the table is missing in one of them.
+    pub fn is_difference(&self) -> bool {
+        !matches!(self, Self::NoDiff(_))
+    }
+
+    /// Labels the source of the output, if any, with the alias given to its database.
+    pub fn with_source_aliases(
+        self,
+        first_alias: Option<&str>,
+        second_alias: Option<&str>,
+    ) -> Self {
+        match self {
+            Self::NotExists(table, source) => {
+                Self::NotExists(table, source.with_alias(first_alias, second_alias))
+            }
+            output => output,
+        }
+    }
+
+    /// Returns the plain, uncolored message of the output (e.g. for machine-readable reports).
+    pub fn message(&self) -> String {
+        match self {
+            Self::NoDiff(table) => format!("{} - No difference in columns", table),
+            Self::NotExists(table, source) => format!("{} - Does not exist in {}", table, source),
+            Self::Added(table, column) => {
+                format!("{}.{} - Column only exists in second", table, column)
+            }
+            Self::Removed(table, column) => {
+                format!("{}.{} - Column only exists in first", table, column)
+            }
+            Self::DefinitionChanged(table, diff) => format!(
+                "{}.{} - Column definition differs ({})",
+                table,
+                diff.first().name(),
+                diff.changes().join(", ")
+            ),
+        }
+    }
+
+    /// Converts the column difference output to a colored string.
+    pub fn to_string(&self) -> ColoredString {
+        let message = self.message();
+        match self {
+            Self::NoDiff(_) => message.green().bold(),
+            Self::NotExists(_, _) => message.red().bold().underline(),
+            _ => message.red().bold(),
+        }
+    }
+}
+
+impl DiffOutputMarker for ColumnDiffOutput {
+    fn convert(self, schema_name: SchemaName) -> DiffOutput {
+        DiffOutput::SchemaDiff(schema_name, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_definition_diff_changes() {
+        let first = TableColumn::new("price", "integer", false, None);
+        let second = TableColumn::new("price", "bigint", true, Some("0".to_string()));
+        let diff = ColumnDefinitionDiff::new(first.clone(), second);
+
+        assert!(diff.has_changes());
+        assert_eq!(
+            diff.changes(),
+            vec![
+                "type: integer -> bigint".to_string(),
+                "nullable: false -> true".to_string(),
+                "default: none -> 0".to_string(),
+            ]
+        );
+        assert!(!ColumnDefinitionDiff::new(first.clone(), first).has_changes());
+    }
+
+    #[test]
+    fn test_column_definition_diff_comment_changes() {
+        let first = TableColumn::new("price", "integer", false, None)
+            .with_comment(Some("Price in cents".to_string()));
+        let second = TableColumn::new("price", "integer", false, None)
+            .with_comment(Some("Price in euros".to_string()));
+
+        assert_eq!(
+            ColumnDefinitionDiff::new(first.clone(), second).changes(),
+            vec!["comment: Price in cents -> Price in euros".to_string()]
+        );
+        assert_eq!(
+            ColumnDefinitionDiff::new(TableColumn::new("price", "integer", false, None), first)
+                .changes(),
+            vec!["comment: none -> Price in cents".to_string()]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use anyhow::Result;
+use colored::Colorize;
+use tracing::{debug, info};
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::diff_payload::DiffPayload;
+use crate::diff::schema::output::{ColumnDefinitionDiff, ColumnDiffOutput};
+use crate::diff::table::query::input::{QueryTableColumnsInput, QueryTableNamesInput};
+use crate::diff::table::query::output::TableSource;
+use crate::diff::table::query::table_query_executor::{
+    TableDualSourceQueryExecutor, TableSingleSourceQueryExecutor,
+};
+use crate::diff::table::query::table_types::{TableColumn, TableName};
+use crate::diff::types::{DiffOutputMarker, SchemaName};
+use std::time::Instant;
+
+pub struct SchemaDiffer<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor> {
+    single_table_query_executor: TQE,
+    dual_table_query_executor: DTQE,
+}
+
+impl<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor>
+    SchemaDiffer<TQE, DTQE>
+{
+    pub fn new(single_table_query_executor: TQE, dual_table_query_executor: DTQE) -> Self {
+        Self {
+            single_table_query_executor,
+            dual_table_query_executor,
+        }
+    }
+
+    pub async fn diff_all_table_schemas(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: String,
+    ) -> Result<Vec<DiffOutput>> {
+        info!("{}", "Starting schema analysis…".yellow().bold());
+
+        let mut tables = self
+            .get_all_tables(diff_payload, schema_name.to_owned())
+            .await?;
+
+        tables.sort_by_key(|s| s.to_lowercase());
+
+        let schema_name = &schema_name;
+        let futures = tables.iter().map(|table_name| async move {
+            let start = Instant::now();
+
+            let input = QueryTableColumnsInput::new(
+                SchemaName::new(schema_name.to_owned()),
+                TableName::new(table_name.to_owned()),
+            )
+            .with_second_table_name(TableName::new(diff_payload.second_table_name(table_name)));
+
+            let (first_result, second_result) = self
+                .dual_table_query_executor
+                .query_table_columns(input)
+                .await;
+
+            let column_diff_results = Self::extract_result(table_name, first_result, second_result)
+                .into_iter()
+                .map(|output| {
+                    output.with_source_aliases(
+                        diff_payload.first_alias(),
+                        diff_payload.second_alias(),
+                    )
+                })
+                .collect::<Vec<ColumnDiffOutput>>();
+
+            debug!(
+                "Columns for {} analyzed in: {}ms",
+                table_name,
+                start.elapsed().as_millis()
+            );
+
+            column_diff_results
+        });
+
+        let start = Instant::now();
+        let analysed_tables = futures::future::join_all(futures).await;
+        let elapsed = start.elapsed();
+        info!(
+            "{}",
+            format!(
+                "Total schema analysis completed in: {}ms",
+                elapsed.as_millis()
+            )
+            .yellow()
+            .bold(),
+        );
+
+        let column_diff_results = analysed_tables
+            .into_iter()
+            .flatten()
+            .collect::<Vec<ColumnDiffOutput>>();
+
+        info!("##############################################");
+        info!("{}", "Schema analysis results 👇".bright_magenta().bold());
+
+        for column_diff_result in column_diff_results
+            .iter()
+            .filter(|output| !diff_payload.only_differences() || output.is_difference())
+        {
+            info!("{}", column_diff_result.to_string());
+        }
+
+        info!("##############################################");
+
+        Ok(column_diff_results
+            .into_iter()
+            .map(|diff| diff.convert(SchemaName::new(schema_name.to_owned())))
+            .collect())
+    }
+
+    pub async fn get_all_tables(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: String,
+    ) -> Result<Vec<String>> {
+        let input = QueryTableNamesInput::new(
+            SchemaName::new(schema_name),
+            diff_payload.included_tables().to_vec(),
+            diff_payload.excluded_tables().to_vec(),
+        );
+
+        let tables = self
+            .single_table_query_executor
+            .query_table_names(input)
+            .await;
+
+        Ok(tables)
+    }
+
+    fn extract_result(
+        table_name: &str,
+        first_result: Result<Vec<TableColumn>>,
+        second_result: Result<Vec<TableColumn>>,
+    ) -> Vec<ColumnDiffOutput> {
+        let (first_columns, second_columns) = match (first_result, second_result) {
+            (Ok(first_columns), Ok(second_columns)) => (first_columns, second_columns),
+            (Err(_e), _) => {
+                return vec![ColumnDiffOutput::NotExists(
+                    table_name.to_owned(),
+                    TableSource::First(None),
+                )]
+            }
+            (_, Err(_e)) => {
+                return vec![ColumnDiffOutput::NotExists(
+                    table_name.to_owned(),
+                    TableSource::Second(None),
+                )]
+            }
+        };
+
+        // A table without any columns in `information_schema` does not exist on that side
+        if first_columns.is_empty() && !second_columns.is_empty() {
+            return vec![ColumnDiffOutput::NotExists(
+                table_name.to_owned(),
+                TableSource::First(None),
+            )];
+        }
+
+        if second_columns.is_empty() && !first_columns.is_empty() {
+            return vec![ColumnDiffOutput::NotExists(
+                table_name.to_owned(),
+                TableSource::Second(None),
+            )];
+        }
+
+        let mut column_diffs = Vec::new();
+
+        for first_column in &first_columns {
+            let second_column = second_columns
+                .iter()
+                .find(|column| column.name() == first_column.name());
+
+            match second_column {
+                None => column_diffs.push(ColumnDiffOutput::Removed(
+                    table_name.to_owned(),
+                    first_column.name().to_owned(),
+                )),
+                Some(second_column) => {
+                    let definition_diff =
+                        ColumnDefinitionDiff::new(first_column.clone(), second_column.clone());
+
+                    if definition_diff.has_changes() {
+                        column_diffs.push(ColumnDiffOutput::DefinitionChanged(
+                            table_name.to_owned(),
+                            Box::new(definition_diff),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for second_column in &second_columns {
+            let exists_in_first = first_columns
+                .iter()
+                .any(|column| column.name() == second_column.name());
+
+            if !exists_in_first {
+                column_diffs.push(ColumnDiffOutput::Added(
+                    table_name.to_owned(),
+                    second_column.name().to_owned(),
+                ));
+            }
+        }
+
+        if column_diffs.is_empty() {
+            return vec![ColumnDiffOutput::NoDiff(table_name.to_owned())];
+        }
+
+        column_diffs
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use crate::diff::diff_output::DiffOutput;
+    use crate::diff::diff_payload::DiffPayload;
+    use crate::diff::schema::output::ColumnDiffOutput;
+    use crate::diff::schema::schema_differ::SchemaDiffer;
+    use crate::diff::table::query::table_query_executor::{
+        MockTableDualSourceQueryExecutor, MockTableSingleSourceQueryExecutor,
+    };
+    use crate::diff::table::query::table_types::TableColumn;
+
+    const EMPTY_STRING_VEC: Vec<String> = Vec::new();
+
+    fn diff_payload() -> DiffPayload {
+        DiffPayload::builder()
+            .first_db("first_db")
+            .second_db("second_db")
+            .only_tables(false)
+            .only_sequences(false)
+            .only_count(false)
+            .chunk_size(10000)
+            .start_position(0)
+            .max_connections(10)
+            .include_tables(vec!["table1"])
+            .exclude_tables(EMPTY_STRING_VEC)
+            .schema_name("schema_name")
+            .accept_invalid_certs_first_db(false)
+            .accept_invalid_certs_second_db(false)
+            .include_schema(true)
+            .build()
+    }
+
+    fn column_diffs(diff_output: Vec<DiffOutput>) -> Vec<ColumnDiffOutput> {
+        diff_output
+            .into_iter()
+            .map(|diff| match diff {
+                DiffOutput::SchemaDiff(_, column_diff) => column_diff,
+                _ => panic!("Expected DiffOutput::SchemaDiff"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_table_schemas_when_same_columns() {
+        let mut single_source_query_executor = MockTableSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockTableDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_table_names()
+            .times(1)
+            .returning(|_| vec!["table1".to_string()]);
+
+        dual_source_query_executor
+            .expect_query_table_columns()
+            .times(1)
+            .returning(|_| {
+                let columns = vec![TableColumn::new("id", "integer", false, None)];
+                (Ok(columns.clone()), Ok(columns))
+            });
+
+        let schema_differ =
+            SchemaDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let diff_output = schema_differ
+            .diff_all_table_schemas(&diff_payload(), "schema_name".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            column_diffs(diff_output),
+            vec![ColumnDiffOutput::NoDiff("table1".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_table_schemas_when_columns_differ() {
+        let mut single_source_query_executor = MockTableSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockTableDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_table_names()
+            .times(1)
+            .returning(|_| vec!["table1".to_string()]);
+
+        dual_source_query_executor
+            .expect_query_table_columns()
+            .times(1)
+            .returning(|_| {
+                let first_columns = vec![
+                    TableColumn::new("id", "integer", false, None),
+                    TableColumn::new("legacy", "text", true, None),
+                    TableColumn::new("price", "integer", false, None),
+                ];
+                let second_columns = vec![
+                    TableColumn::new("id", "integer", false, None),
+                    TableColumn::new("price", "bigint", false, None),
+                    TableColumn::new("notes", "text", true, None),
+                ];
+                (Ok(first_columns), Ok(second_columns))
+            });
+
+        let schema_differ =
+            SchemaDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let diff_output = schema_differ
+            .diff_all_table_schemas(&diff_payload(), "schema_name".to_string())
+            .await
+            .unwrap();
+
+        let column_diffs = column_diffs(diff_output);
+
+        assert_eq!(column_diffs.len(), 3);
+        assert_eq!(
+            column_diffs[0],
+            ColumnDiffOutput::Removed("table1".to_string(), "legacy".to_string())
+        );
+        match &column_diffs[1] {
+            ColumnDiffOutput::DefinitionChanged(table_name, diff) => {
+                assert_eq!("table1", table_name);
+                assert_eq!("price", diff.first().name());
+                assert_eq!(vec!["type: integer -> bigint".to_string()], diff.changes());
+            }
+            _ => panic!("Expected ColumnDiffOutput::DefinitionChanged"),
+        }
+        assert_eq!(
+            column_diffs[2],
+            ColumnDiffOutput::Added("table1".to_string(), "notes".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_table_schemas_when_column_comments_differ() {
+        let mut single_source_query_executor = MockTableSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockTableDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_table_names()
+            .times(1)
+            .returning(|_| vec!["table1".to_string()]);
+
+        dual_source_query_executor
+            .expect_query_table_columns()
+            .times(1)
+            .returning(|_| {
+                let first_columns = vec![
+                    TableColumn::new("id", "integer", false, None),
+                    TableColumn::new("price", "integer", false, None),
+                ];
+                let second_columns = vec![
+                    TableColumn::new("id", "integer", false, None),
+                    TableColumn::new("price", "integer", false, None)
+                        .with_comment(Some("Price in cents".to_string())),
+                ];
+                (Ok(first_columns), Ok(second_columns))
+            });
+
+        let schema_differ =
+            SchemaDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let diff_output = schema_differ
+            .diff_all_table_schemas(&diff_payload(), "schema_name".to_string())
+            .await
+            .unwrap();
+
+        let column_diffs = column_diffs(diff_output);
+
+        assert_eq!(column_diffs.len(), 1);
+        match &column_diffs[0] {
+            ColumnDiffOutput::DefinitionChanged(table_name, diff) => {
+                assert_eq!("table1", table_name);
+                assert_eq!("price", diff.first().name());
+                assert_eq!(
+                    vec!["comment: none -> Price in cents".to_string()],
+                    diff.changes()
+                );
+            }
+            _ => panic!("Expected ColumnDiffOutput::DefinitionChanged"),
+        }
+    }
+}
@@ -63,3 +63,40 @@ impl QueryLastValuesInput {
         &self.1
     }
 }
+
+/// Represents the input for querying the definition of a sequence.
+pub struct QuerySequenceMetadataInput(SchemaName, SequenceName);
+
+impl QuerySequenceMetadataInput {
+    /// Creates a new `QuerySequenceMetadataInput` with the given sequence name.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_name` - The name of the schema the sequence belongs to.
+    /// * `sequence_name` - The name of the sequence to query.
+    ///
+    /// # Returns
+    ///
+    /// A new `QuerySequenceMetadataInput` instance.
+    pub fn new(schema_name: SchemaName, sequence_name: SequenceName) -> Self {
+        Self(schema_name, sequence_name)
+    }
+
+    /// Returns the schema name.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the schema name.
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.0
+    }
+
+    /// Returns the sequence name.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the sequence name.
+    pub fn sequence_name(&self) -> &SequenceName {
+        &self.1
+    }
+}
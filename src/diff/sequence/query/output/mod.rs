@@ -1,32 +1,49 @@
 use crate::diff::diff_output::DiffOutput;
-use crate::diff::types::DiffOutputMarker;
+use crate::diff::sequence::query::sequence_types::{SequenceMetadata, SequenceOwner};
+use crate::diff::types::{DiffOutputMarker, SchemaName};
 use colored::{ColoredString, Colorize};
 use std::fmt::Display;
 
-/// Represents the source of a sequence.
+/// Represents the source of a sequence, along with the alias given to its database, if any.
 #[derive(Clone)]
 pub enum SequenceSource {
-    First,
-    Second,
+    First(Option<String>),
+    Second(Option<String>),
+}
+
+impl SequenceSource {
+    /// Labels the source with the alias given to its database, if any.
+    pub fn with_alias(self, first_alias: Option<&str>, second_alias: Option<&str>) -> Self {
+        match self {
+            Self::First(_) => Self::First(first_alias.map(str::to_owned)),
+            Self::Second(_) => Self::Second(second_alias.map(str::to_owned)),
+        }
+    }
 }
 
 impl Display for SequenceSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::First => write!(f, "first"),
-            Self::Second => write!(f, "second"),
+            Self::First(alias) => write!(f, "{}", alias.as_deref().unwrap_or("first")),
+            Self::Second(alias) => write!(f, "{}", alias.as_deref().unwrap_or("second")),
         }
     }
 }
 
 /// Represents the difference in count between two sequences.
 #[derive(Clone)]
-pub struct SequenceCountDiff(i64, i64);
+pub struct SequenceCountDiff(i64, i64, Option<SequenceStructureDiff>);
 
 impl SequenceCountDiff {
     /// Creates a new `SequenceCountDiff` instance with the given counts.
     pub fn new(first: i64, second: i64) -> Self {
-        Self(first, second)
+        Self(first, second, None)
+    }
+
+    /// Attaches how the definitions of the sequences differ, if they do.
+    pub fn with_structure_diff(mut self, structure_diff: Option<SequenceStructureDiff>) -> Self {
+        self.2 = structure_diff;
+        self
     }
 
     /// Returns the count of the first sequence.
@@ -38,6 +55,128 @@ impl SequenceCountDiff {
     pub fn second(&self) -> i64 {
         self.1
     }
+
+    pub fn structure_diff(&self) -> Option<&SequenceStructureDiff> {
+        self.2.as_ref()
+    }
+}
+
+/// Represents the difference in the definition of two sequences.
+#[derive(Clone)]
+pub struct SequenceStructureDiff(SequenceMetadata, SequenceMetadata);
+
+impl SequenceStructureDiff {
+    /// Creates a new `SequenceStructureDiff` instance with the given definitions.
+    pub fn new(first: SequenceMetadata, second: SequenceMetadata) -> Self {
+        Self(first, second)
+    }
+
+    pub fn first(&self) -> &SequenceMetadata {
+        &self.0
+    }
+
+    pub fn second(&self) -> &SequenceMetadata {
+        &self.1
+    }
+
+    /// Returns a description of each attribute that differs between the two definitions.
+    pub fn changes(&self) -> Vec<String> {
+        let (first, second) = (self.first(), self.second());
+        let mut changes = Vec::new();
+
+        if first.data_type() != second.data_type() {
+            changes.push(format!(
+                "type: {} -> {}",
+                first.data_type(),
+                second.data_type()
+            ));
+        }
+
+        if first.increment_by() != second.increment_by() {
+            changes.push(format!(
+                "increment: {} -> {}",
+                first.increment_by(),
+                second.increment_by()
+            ));
+        }
+
+        if first.min_value() != second.min_value() {
+            changes.push(format!(
+                "min: {} -> {}",
+                first.min_value(),
+                second.min_value()
+            ));
+        }
+
+        if first.max_value() != second.max_value() {
+            changes.push(format!(
+                "max: {} -> {}",
+                first.max_value(),
+                second.max_value()
+            ));
+        }
+
+        if first.cache_size() != second.cache_size() {
+            changes.push(format!(
+                "cache: {} -> {}",
+                first.cache_size(),
+                second.cache_size()
+            ));
+        }
+
+        if first.cycle() != second.cycle() {
+            changes.push(format!("cycle: {} -> {}", first.cycle(), second.cycle()));
+        }
+
+        changes
+    }
+
+    /// Determines whether the two sequence definitions differ.
+    pub fn has_changes(&self) -> bool {
+        !self.changes().is_empty()
+    }
+}
+
+/// Represents the difference in the column owning two sequences, e.g. a sequence detached from its
+/// `SERIAL` column after a restore, or backing an `IDENTITY` column in only one database.
+#[derive(Clone)]
+pub struct SequenceOwnershipDiff(Option<SequenceOwner>, Option<SequenceOwner>);
+
+impl SequenceOwnershipDiff {
+    /// Creates a new `SequenceOwnershipDiff` instance with the given owners.
+    pub fn new(first: Option<SequenceOwner>, second: Option<SequenceOwner>) -> Self {
+        Self(first, second)
+    }
+
+    pub fn first(&self) -> Option<&SequenceOwner> {
+        self.0.as_ref()
+    }
+
+    pub fn second(&self) -> Option<&SequenceOwner> {
+        self.1.as_ref()
+    }
+
+    /// Determines whether the sequences are owned by different columns, or only one of them is owned.
+    pub fn has_changes(&self) -> bool {
+        self.0 != self.1
+    }
+}
+
+impl Display for SequenceOwnershipDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let owner = |owner: Option<&SequenceOwner>| {
+            owner
+                .map(SequenceOwner::to_string)
+                .unwrap_or_else(|| "none".to_string())
+        };
+
+        write!(
+            f,
+            "owner: {} -> {}",
+            owner(self.first()),
+            owner(self.second())
+        )
+    }
 }
 
 #[derive(Clone)]
@@ -49,40 +188,155 @@ pub enum SequenceDiffOutput {
     NotExists(String, SequenceSource),
     /// Indicates a difference in count between the sequences.
     Diff(String, SequenceCountDiff),
+    /// Indicates that reading the sequence exceeded the statement timeout in a specific source.
+    Timeout(String, SequenceSource),
+    /// Indicates a difference in the definition of the sequences.
+    StructureDiff(String, SequenceStructureDiff),
+    /// Indicates a difference in the column owning the sequences, while their values and definitions match.
+    OwnershipDiff(String, SequenceOwnershipDiff),
 }
 
 impl SequenceDiffOutput {
-    /// Converts the `SequenceDiffOutput` to a colored string representation.
-    pub fn to_string(&self) -> ColoredString {
+    /// Returns the name of the sequence this output refers to.
+    /// Determines whether the sequence differs between the databases, including when it is missing
+    /// in one of them. Sequences that could not be compared are not counted as differences.
+    pub fn is_difference(&self) -> bool {
+        matches!(
+            self,
+            Self::Diff(_, _)
+                | Self::StructureDiff(_, _)
+                | Self::OwnershipDiff(_, _)
+                | Self::NotExists(_, _)
+        )
+    }
+
+    pub fn sequence_name(&self) -> &str {
+        match self {
+            Self::NoDiff(sequence)
+            | Self::NotExists(sequence, _)
+            | Self::Diff(sequence, _)
+            | Self::Timeout(sequence, _)
+            | Self::StructureDiff(sequence, _)
+            | Self::OwnershipDiff(sequence, _) => sequence,
+        }
+    }
+
+    /// Reports the given ownership difference, if any, in place of matching sequences. Other
+    /// differences take precedence, since they already report the sequences as differing.
+    pub fn or_ownership_diff(self, ownership_diff: Option<SequenceOwnershipDiff>) -> Self {
+        match (self, ownership_diff) {
+            (Self::NoDiff(sequence), Some(ownership_diff)) => {
+                Self::OwnershipDiff(sequence, ownership_diff)
+            }
+            (output, _) => output,
+        }
+    }
+
+    /// Labels the source of the output, if any, with the alias given to its database.
+    pub fn with_source_aliases(
+        self,
+        first_alias: Option<&str>,
+        second_alias: Option<&str>,
+    ) -> Self {
         match self {
-            Self::NoDiff(sequence) => format!("{} - No difference\n", sequence).green().bold(),
             Self::NotExists(sequence, source) => {
-                format!("{} - Does not exist in {}\n", sequence, source)
-                    .red()
-                    .bold()
-                    .underline()
+                Self::NotExists(sequence, source.with_alias(first_alias, second_alias))
+            }
+            Self::Timeout(sequence, source) => {
+                Self::Timeout(sequence, source.with_alias(first_alias, second_alias))
+            }
+            output => output,
+        }
+    }
+
+    /// Returns the plain, uncolored message of the output (e.g. for machine-readable reports).
+    pub fn message(&self) -> String {
+        match self {
+            Self::NoDiff(sequence) => format!("{} - No difference", sequence),
+            Self::NotExists(sequence, source) => {
+                format!("{} - Does not exist in {}", sequence, source)
             }
             Self::Diff(sequence, diffs) => format!(
-                "Difference in sequence:{} - First: {}, Second: {}\n",
+                "Difference in sequence:{} - First: {}, Second: {}{}",
                 sequence,
                 diffs.first(),
-                diffs.second()
-            )
-            .red()
-            .bold()
-            .underline(),
+                diffs.second(),
+                diffs
+                    .structure_diff()
+                    .map(|structure_diff| format!(", {}", structure_diff.changes().join(", ")))
+                    .unwrap_or_default()
+            ),
+            Self::Timeout(sequence, source) => {
+                format!("{} - Query timed out in {}", sequence, source)
+            }
+            Self::StructureDiff(sequence, diff) => format!(
+                "Difference in sequence definition:{} - {}",
+                sequence,
+                diff.changes().join(", ")
+            ),
+            Self::OwnershipDiff(sequence, diff) => {
+                format!("Difference in sequence ownership:{} - {}", sequence, diff)
+            }
+        }
+    }
+
+    /// Converts the `SequenceDiffOutput` to a colored string representation, on its own line.
+    pub fn to_string(&self) -> ColoredString {
+        let message = format!("{}\n", self.message());
+        match self {
+            Self::NoDiff(_) => message.green().bold(),
+            _ => message.red().bold().underline(),
         }
     }
 }
 
 impl DiffOutputMarker for SequenceDiffOutput {
-    fn convert(self) -> DiffOutput {
-        DiffOutput::SequenceDiff(self.clone())
+    fn convert(self, schema_name: SchemaName) -> DiffOutput {
+        DiffOutput::SequenceDiff(schema_name, self)
     }
 }
 
-impl From<SequenceDiffOutput> for DiffOutput {
-    fn from(val: SequenceDiffOutput) -> Self {
-        DiffOutput::SequenceDiff(val)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_is_plain_text() {
+        let diff = SequenceDiffOutput::Diff("sequence1".to_string(), SequenceCountDiff::new(2, 1));
+        let not_exists = SequenceDiffOutput::NotExists(
+            "sequence1".to_string(),
+            SequenceSource::Second(Some("staging".to_string())),
+        );
+
+        assert_eq!(
+            "Difference in sequence:sequence1 - First: 2, Second: 1",
+            diff.message()
+        );
+        assert_eq!(
+            "sequence1 - Does not exist in staging",
+            not_exists.message()
+        );
+        // The colored variant keeps rendering each result on its own line
+        assert_eq!(format!("{}\n", diff.message()), &*diff.to_string());
+    }
+
+    #[test]
+    fn test_ownership_diff() {
+        let identity = SequenceOwner::new("orders", "id", true);
+        let owned = SequenceOwner::new("orders", "id", false);
+
+        let detached = SequenceDiffOutput::OwnershipDiff(
+            "orders_id_seq".to_string(),
+            SequenceOwnershipDiff::new(Some(owned.clone()), None),
+        );
+
+        assert!(detached.is_difference());
+        assert_eq!(
+            "Difference in sequence ownership:orders_id_seq - owner: orders.id (owned) -> none",
+            detached.message()
+        );
+        assert!(SequenceOwnershipDiff::new(Some(identity.clone()), Some(owned)).has_changes());
+        assert!(!SequenceOwnershipDiff::new(Some(identity.clone()), Some(identity)).has_changes());
+        assert!(!SequenceOwnershipDiff::new(None, None).has_changes());
     }
 }
@@ -6,8 +6,11 @@ use std::fmt::{Display, Formatter};
 pub enum SequenceQuery {
     /// Retrieves the last value of a specific sequence.
     LastValue(SchemaName, SequenceName),
-    /// Retrieves all sequences in the database.
+    /// Retrieves all sequences of a schema, along with the schema they belong to.
     AllSequences(SchemaName),
+    /// Retrieves the definition (type, increment and bounds) of a specific sequence, along with
+    /// the `SERIAL` or `IDENTITY` column owning it, if any.
+    Metadata(SchemaName, SequenceName),
 }
 
 impl Display for SequenceQuery {
@@ -26,13 +29,34 @@ impl Display for SequenceQuery {
                 write!(
                     f,
                     r#"
-                    SELECT sequence_name
+                    SELECT sequence_schema, sequence_name
                     FROM information_schema.sequences
                     WHERE sequence_schema = '{}';
                     "#,
                     schema_name.name()
                 )
             }
+            SequenceQuery::Metadata(schema_name, sequence_name) => {
+                write!(
+                    f,
+                    r#"
+                    SELECT s.data_type::text AS data_type, s.increment_by, s.min_value, s.max_value, s.cache_size, s.cycle,
+                    owner_table.relname::text AS owner_table, owner_column.attname::text AS owner_column, d.deptype::text AS owner_type
+                    FROM pg_sequences s
+                    LEFT JOIN pg_depend d
+                    ON d.classid = 'pg_class'::regclass
+                    AND d.objid = format('%I.%I', s.schemaname, s.sequencename)::regclass
+                    AND d.refclassid = 'pg_class'::regclass
+                    AND d.deptype IN ('a', 'i')
+                    LEFT JOIN pg_class owner_table ON owner_table.oid = d.refobjid
+                    LEFT JOIN pg_attribute owner_column ON owner_column.attrelid = d.refobjid AND owner_column.attnum = d.refobjsubid
+                    WHERE s.schemaname = '{}'
+                    AND s.sequencename = '{}';
+                    "#,
+                    schema_name.name(),
+                    sequence_name.name()
+                )
+            }
         }
     }
 }
@@ -67,7 +91,27 @@ mod tests {
 
         assert_eq!(
             sanitize_raw_string(all_sequences_query),
-            "SELECT sequence_name FROM information_schema.sequences WHERE sequence_schema = 'test_schema';"
+            "SELECT sequence_schema, sequence_name FROM information_schema.sequences WHERE sequence_schema = 'test_schema';"
+        );
+    }
+
+    #[test]
+    fn test_sequence_metadata_query() {
+        let schema_name = SchemaName::new("test_schema");
+        let sequence_name = SequenceName::new("test_sequence");
+        let metadata_query = SequenceQuery::Metadata(schema_name, sequence_name);
+
+        assert_eq!(
+            sanitize_raw_string(metadata_query),
+            "SELECT s.data_type::text AS data_type, s.increment_by, s.min_value, s.max_value, s.cache_size, s.cycle, \
+             owner_table.relname::text AS owner_table, owner_column.attname::text AS owner_column, d.deptype::text AS owner_type \
+             FROM pg_sequences s \
+             LEFT JOIN pg_depend d ON d.classid = 'pg_class'::regclass \
+             AND d.objid = format('%I.%I', s.schemaname, s.sequencename)::regclass \
+             AND d.refclassid = 'pg_class'::regclass AND d.deptype IN ('a', 'i') \
+             LEFT JOIN pg_class owner_table ON owner_table.oid = d.refobjid \
+             LEFT JOIN pg_attribute owner_column ON owner_column.attrelid = d.refobjid AND owner_column.attnum = d.refobjsubid \
+             WHERE s.schemaname = 'test_schema' AND s.sequencename = 'test_sequence';"
         );
     }
 }
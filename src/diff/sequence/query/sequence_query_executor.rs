@@ -1,12 +1,16 @@
+use crate::diff::db_pool::{get_client, POOL_WAIT_TIMEOUT};
 /// This module contains the implementation of query executors for sequence-related operations.
 /// It provides traits and structs for executing queries on a single data source and on dual data sources.
 /// The single data source executor is responsible for querying sequence names.
 /// The dual data source executor is responsible for querying sequence last values.
-/// Both executors use the `sqlx` crate for interacting with the database.
+/// Both executors query the database through `deadpool_postgres` pools. Cloning a pool shares its
+/// connections, so executors built from clones of the same pool never exceed its `max_connections`.
 ///
 /// # Examples
 ///
 /// ```no_run
+/// use deadpool_postgres::{Config, Pool, Runtime};
+/// use deadpool_postgres::tokio_postgres::NoTls;
 /// use rust_pgdatadiff::diff::sequence::query::sequence_query_executor::SequenceSingleSourceQueryExecutorImpl;
 /// use rust_pgdatadiff::diff::sequence::query::sequence_query_executor::SequenceSingleSourceQueryExecutor;
 /// use rust_pgdatadiff::diff::sequence::query::input::QueryAllSequencesInput;
@@ -60,11 +64,18 @@
 ///         .await;
 /// }
 /// ```
-use crate::diff::sequence::query::input::{QueryAllSequencesInput, QueryLastValuesInput};
+use crate::diff::db_query;
+use crate::diff::dry_run::QueryLog;
+use crate::diff::sequence::query::input::{
+    QueryAllSequencesInput, QueryLastValuesInput, QuerySequenceMetadataInput,
+};
 use crate::diff::sequence::query::sequence_query::SequenceQuery;
+use crate::diff::sequence::query::sequence_types::{SequenceMetadata, SequenceName, SequenceOwner};
+use crate::diff::types::{QueryTimeoutError, SchemaName};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use deadpool_postgres::tokio_postgres::Row;
 use deadpool_postgres::Pool;
 use tracing::error;
 
@@ -75,38 +86,63 @@ pub trait SequenceSingleSourceQueryExecutor {
     ///
     /// # Returns
     ///
-    /// A vector of sequence names.
-    async fn query_sequence_names(&self, input: QueryAllSequencesInput) -> Vec<String>;
+    /// A vector of sequence names, each qualified with the schema it belongs to.
+    async fn query_sequence_names(&self, input: QueryAllSequencesInput) -> Vec<SequenceName>;
 }
 
 pub struct SequenceSingleSourceQueryExecutorImpl {
     db_pool: Pool,
+    query_log: Option<QueryLog>,
 }
 
 impl SequenceSingleSourceQueryExecutorImpl {
     pub fn new(db_pool: Pool) -> Self {
-        Self { db_pool }
+        Self {
+            db_pool,
+            query_log: None,
+        }
+    }
+
+    /// Records every executed query in the given log, if any (dry-run mode).
+    pub fn with_query_log(mut self, query_log: Option<QueryLog>) -> Self {
+        self.query_log = query_log;
+        self
     }
 }
 
 #[async_trait]
 impl SequenceSingleSourceQueryExecutor for SequenceSingleSourceQueryExecutorImpl {
-    async fn query_sequence_names(&self, input: QueryAllSequencesInput) -> Vec<String> {
-        // Clone the database client
-        let client = self.db_pool.get().await.unwrap();
+    async fn query_sequence_names(&self, input: QueryAllSequencesInput) -> Vec<SequenceName> {
+        // Wait for a connection to free up, rather than panicking while the pool is exhausted
+        let client = match get_client(&self.db_pool, POOL_WAIT_TIMEOUT).await {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Error while fetching sequence names: {}", e);
+                return vec![];
+            }
+        };
 
         let schema_name = input.schema_name();
+        let group = format!("schema {}", schema_name.name());
         let sequence_query = SequenceQuery::AllSequences(schema_name);
 
         let query_binding = sequence_query.to_string();
 
-        client
-            .query(&query_binding, &[])
+        if let Some(query_log) = &self.query_log {
+            query_log.record(group, &query_binding);
+        }
+
+        db_query::query(&client, &query_binding)
             .await
             .unwrap()
             .into_iter()
-            .map(|row| row.get("sequence_name"))
-            .collect::<Vec<String>>()
+            .map(|row| {
+                SequenceName::qualified(
+                    SchemaName::new(row.get::<_, String>("sequence_schema")),
+                    row.get::<_, String>("sequence_name"),
+                )
+            })
+            .collect::<Vec<SequenceName>>()
     }
 }
 
@@ -126,11 +162,26 @@ pub trait SequenceDualSourceQueryExecutor {
         &self,
         input: QueryLastValuesInput,
     ) -> (Result<i64>, Result<i64>);
+
+    /// Executes a query to retrieve the definition of a sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input parameters for the query.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the definition of the sequence in each database.
+    async fn query_sequence_metadata(
+        &self,
+        input: QuerySequenceMetadataInput,
+    ) -> (Result<SequenceMetadata>, Result<SequenceMetadata>);
 }
 
 pub struct SequenceDualSourceQueryExecutorImpl {
     first_db_pool: Pool,
     second_db_pool: Pool,
+    query_log: Option<QueryLog>,
 }
 
 impl SequenceDualSourceQueryExecutorImpl {
@@ -138,8 +189,15 @@ impl SequenceDualSourceQueryExecutorImpl {
         Self {
             first_db_pool,
             second_db_pool,
+            query_log: None,
         }
     }
+
+    /// Records every query in the given log, if any, instead of executing data queries (dry-run mode).
+    pub fn with_query_log(mut self, query_log: Option<QueryLog>) -> Self {
+        self.query_log = query_log;
+        self
+    }
 }
 
 #[async_trait]
@@ -148,10 +206,6 @@ impl SequenceDualSourceQueryExecutor for SequenceDualSourceQueryExecutorImpl {
         &self,
         input: QueryLastValuesInput,
     ) -> (Result<i64>, Result<i64>) {
-        // Clone the database clients
-        let first_client = self.first_db_pool.get().await.unwrap();
-        let second_client = self.second_db_pool.get().await.unwrap();
-
         let sequence_query = SequenceQuery::LastValue(
             input.schema_name().to_owned(),
             input.sequence_name().to_owned(),
@@ -159,28 +213,115 @@ impl SequenceDualSourceQueryExecutor for SequenceDualSourceQueryExecutorImpl {
 
         let query_binding = sequence_query.to_string();
 
-        let first_result = first_client.query_one(&query_binding, &[]);
-        let second_result = second_client.query_one(&query_binding, &[]);
+        // In dry-run mode, record the query and report identical values
+        if let Some(query_log) = &self.query_log {
+            query_log.record(
+                format!("sequence {}", input.sequence_name().name()),
+                &query_binding,
+            );
+            return (Ok(0), Ok(0));
+        }
 
-        let (first_result, second_result) =
-            futures::future::join(first_result, second_result).await;
+        let query_last_value = |db_pool: &Pool, db: &'static str| {
+            let query_binding = &query_binding;
+            let db_pool = db_pool.clone();
+            async move {
+                let client = get_client(&db_pool, POOL_WAIT_TIMEOUT)
+                    .await
+                    .inspect_err(|e| error!("Error while fetching {} sequence: {}", db, e))?;
 
-        let first_count: Result<i64> = match first_result {
-            Ok(pg_row) => Ok(pg_row.try_get("last_value").unwrap()),
-            Err(e) => {
-                error!("Error while fetching first sequence: {}", e);
-                Err(anyhow::anyhow!("Failed to fetch count for first sequence"))
+                match db_query::query_one(&client, query_binding).await {
+                    Ok(pg_row) => pg_row.try_get("last_value").map_err(|e| {
+                        error!("Error while reading {} sequence: {}", db, e);
+                        anyhow!("Failed to read the last value of {} sequence: {}", db, e)
+                    }),
+                    Err(e) => {
+                        error!("Error while fetching {} sequence: {}", db, e);
+                        Err(QueryTimeoutError::map_error(
+                            e,
+                            &format!("Failed to fetch count for {} sequence", db),
+                        ))
+                    }
+                }
             }
         };
 
-        let second_count: Result<i64> = match second_result {
-            Ok(pg_row) => Ok(pg_row.try_get("last_value").unwrap()),
-            Err(e) => {
-                error!("Error while fetching second sequence: {}", e);
-                Err(anyhow::anyhow!("Failed to fetch count for second sequence"))
+        futures::future::join(
+            query_last_value(&self.first_db_pool, "first"),
+            query_last_value(&self.second_db_pool, "second"),
+        )
+        .await
+    }
+    async fn query_sequence_metadata(
+        &self,
+        input: QuerySequenceMetadataInput,
+    ) -> (Result<SequenceMetadata>, Result<SequenceMetadata>) {
+        let sequence_query = SequenceQuery::Metadata(
+            input.schema_name().to_owned(),
+            input.sequence_name().to_owned(),
+        );
+
+        let query_binding = sequence_query.to_string();
+
+        if let Some(query_log) = &self.query_log {
+            query_log.record(
+                format!("sequence {}", input.sequence_name().name()),
+                &query_binding,
+            );
+        }
+
+        let query_metadata = |db_pool: &Pool, db: &'static str| {
+            let query_binding = &query_binding;
+            let db_pool = db_pool.clone();
+            async move {
+                let client = get_client(&db_pool, POOL_WAIT_TIMEOUT)
+                    .await
+                    .inspect_err(|e| {
+                        error!("Error while fetching {} sequence metadata: {}", db, e)
+                    })?;
+
+                match db_query::query_one(&client, query_binding).await {
+                    Ok(pg_row) => Ok(map_sequence_metadata(&pg_row)),
+                    Err(e) => {
+                        error!("Error while fetching {} sequence metadata: {}", db, e);
+                        Err(anyhow::anyhow!(
+                            "Failed to fetch metadata for {} sequence",
+                            db
+                        ))
+                    }
+                }
             }
         };
 
-        (first_count, second_count)
+        futures::future::join(
+            query_metadata(&self.first_db_pool, "first"),
+            query_metadata(&self.second_db_pool, "second"),
+        )
+        .await
     }
 }
+
+fn map_sequence_metadata(row: &Row) -> SequenceMetadata {
+    // Sequences backing an IDENTITY column depend on it internally ('i'), others automatically ('a')
+    let owner = match (
+        row.get::<_, Option<String>>("owner_table"),
+        row.get::<_, Option<String>>("owner_column"),
+    ) {
+        (Some(table_name), Some(column_name)) => Some(SequenceOwner::new(
+            table_name,
+            column_name,
+            row.get::<_, Option<String>>("owner_type").as_deref() == Some("i"),
+        )),
+        _ => None,
+    };
+
+    SequenceMetadata::new(
+        row.get::<_, String>("data_type"),
+        row.get("increment_by"),
+        row.get("min_value"),
+        row.get("max_value"),
+        row.get("cache_size"),
+        row.get("cycle"),
+    )
+    .with_owner(owner)
+}
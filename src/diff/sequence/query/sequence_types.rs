@@ -1,12 +1,137 @@
+use std::fmt::Display;
+
+use crate::diff::types::SchemaName;
+
+/// The name of a sequence, along with the schema it belongs to, if known.
 #[derive(Clone)]
-pub struct SequenceName(String);
+pub struct SequenceName(String, Option<SchemaName>);
 
 impl SequenceName {
     pub fn new(name: impl Into<String>) -> Self {
-        Self(name.into())
+        Self(name.into(), None)
+    }
+
+    /// Creates the name of a sequence belonging to the given schema.
+    pub fn qualified(schema_name: SchemaName, name: impl Into<String>) -> Self {
+        Self(name.into(), Some(schema_name))
     }
 
     pub fn name(&self) -> String {
         self.0.to_string()
     }
+
+    pub fn schema_name(&self) -> Option<&SchemaName> {
+        self.1.as_ref()
+    }
+}
+
+/// Represents the definition of a sequence, independent of its current value.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone)]
+pub struct SequenceMetadata {
+    data_type: String,
+    increment_by: i64,
+    min_value: i64,
+    max_value: i64,
+    cache_size: i64,
+    cycle: bool,
+    owner: Option<SequenceOwner>,
+}
+
+impl SequenceMetadata {
+    pub fn new(
+        data_type: impl Into<String>,
+        increment_by: i64,
+        min_value: i64,
+        max_value: i64,
+        cache_size: i64,
+        cycle: bool,
+    ) -> Self {
+        Self {
+            data_type: data_type.into(),
+            increment_by,
+            min_value,
+            max_value,
+            cache_size,
+            cycle,
+            owner: None,
+        }
+    }
+
+    /// Attaches the column owning the sequence, if any.
+    pub fn with_owner(mut self, owner: Option<SequenceOwner>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn data_type(&self) -> &str {
+        &self.data_type
+    }
+
+    pub fn increment_by(&self) -> i64 {
+        self.increment_by
+    }
+
+    pub fn min_value(&self) -> i64 {
+        self.min_value
+    }
+
+    pub fn max_value(&self) -> i64 {
+        self.max_value
+    }
+
+    pub fn cache_size(&self) -> i64 {
+        self.cache_size
+    }
+
+    pub fn cycle(&self) -> bool {
+        self.cycle
+    }
+    /// Returns the column owning the sequence, if it backs a `SERIAL` or `IDENTITY` column.
+    pub fn owner(&self) -> Option<&SequenceOwner> {
+        self.owner.as_ref()
+    }
+}
+
+/// Represents the column owning a sequence, whose values the sequence generates: a `SERIAL`
+/// column (or one the sequence was declared `OWNED BY`), or an `IDENTITY` column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceOwner {
+    table_name: String,
+    column_name: String,
+    identity: bool,
+}
+
+impl SequenceOwner {
+    pub fn new(
+        table_name: impl Into<String>,
+        column_name: impl Into<String>,
+        identity: bool,
+    ) -> Self {
+        Self {
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            identity,
+        }
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    pub fn column_name(&self) -> &str {
+        &self.column_name
+    }
+
+    /// Determines whether the sequence backs an `IDENTITY` column, rather than being owned by a column.
+    pub fn identity(&self) -> bool {
+        self.identity
+    }
+}
+
+impl Display for SequenceOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = if self.identity { "identity" } else { "owned" };
+        write!(f, "{}.{} ({})", self.table_name, self.column_name, kind)
+    }
 }
@@ -1,18 +1,27 @@
 use anyhow::Result;
 use colored::Colorize;
+use futures::{Stream, StreamExt};
 
 use tracing::{debug, info};
 
 use crate::diff::diff_output::DiffOutput;
-use crate::diff::sequence::query::input::{QueryAllSequencesInput, QueryLastValuesInput};
-use crate::diff::sequence::query::output::{SequenceCountDiff, SequenceDiffOutput, SequenceSource};
+use crate::diff::diff_payload::DiffPayload;
+use crate::diff::fail_fast;
+use crate::diff::sequence::query::input::{
+    QueryAllSequencesInput, QueryLastValuesInput, QuerySequenceMetadataInput,
+};
+use crate::diff::sequence::query::output::{
+    SequenceCountDiff, SequenceDiffOutput, SequenceOwnershipDiff, SequenceSource,
+    SequenceStructureDiff,
+};
 use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 
 use crate::diff::sequence::query::sequence_query_executor::{
     SequenceDualSourceQueryExecutor, SequenceSingleSourceQueryExecutor,
 };
-use crate::diff::sequence::query::sequence_types::SequenceName;
-use crate::diff::types::SchemaName;
+use crate::diff::sequence::query::sequence_types::{SequenceMetadata, SequenceName};
+use crate::diff::types::{DiffOutputMarker, QueryTimeoutError, SchemaName};
 
 pub struct SequenceDiffer<
     SQE: SequenceSingleSourceQueryExecutor,
@@ -20,6 +29,7 @@ pub struct SequenceDiffer<
 > {
     single_sequence_query_executor: SQE,
     dual_sequence_query_executor: DSQE,
+    cancellation_token: CancellationToken,
 }
 
 impl<SQE: SequenceSingleSourceQueryExecutor, DSQE: SequenceDualSourceQueryExecutor>
@@ -29,47 +39,24 @@ impl<SQE: SequenceSingleSourceQueryExecutor, DSQE: SequenceDualSourceQueryExecut
         Self {
             single_sequence_query_executor,
             dual_sequence_query_executor,
+            cancellation_token: CancellationToken::new(),
         }
     }
 
-    pub async fn diff_all_sequences(&self, schema_name: String) -> Result<Vec<DiffOutput>> {
-        info!("{}", "Starting sequence analysis…".bold().yellow());
-        let mut sequences = self.get_all_sequences(schema_name.to_owned()).await?;
-
-        sequences.sort_by_key(|s| s.to_lowercase());
-
-        let sorted_sequences = sequences.to_owned();
-
-        let futures = sorted_sequences.iter().map(|sequence_name| async {
-            let start = Instant::now();
-
-            let schema_name = SchemaName::new(schema_name.to_owned());
-            let sequence_name = SequenceName::new(sequence_name.to_owned());
-            let input = QueryLastValuesInput::new(schema_name, sequence_name.to_owned());
-            let (first_result, second_result) = self
-                .dual_sequence_query_executor
-                .query_sequence_last_values(input)
-                .await;
-
-            debug!(
-                "{}",
-                format!("Analyzing sequence: {}", &sequence_name.name())
-                    .yellow()
-                    .bold()
-            );
-
-            let sequence_diff_result =
-                Self::extract_result(sequence_name.name(), first_result, second_result);
-
-            let elapsed = start.elapsed();
-            debug!(
-                "{}",
-                format!("Sequence analysis completed in: {}ms", elapsed.as_millis())
-            );
-            debug!("##############################################");
+    /// Stops comparing sequences once the given token is cancelled, keeping the outputs of the
+    /// sequences compared until then.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = cancellation_token;
+        self
+    }
 
-            sequence_diff_result
-        });
+    pub async fn diff_all_sequences(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: String,
+    ) -> Result<Vec<DiffOutput>> {
+        info!("{}", "Starting sequence analysis…".bold().yellow());
+        let sequences = self.get_sorted_sequences(schema_name.to_owned()).await?;
 
         info!(
             "{}",
@@ -78,7 +65,12 @@ impl<SQE: SequenceSingleSourceQueryExecutor, DSQE: SequenceDualSourceQueryExecut
                 .bold()
         );
         let start = Instant::now();
-        let sequences_analysed = futures::future::join_all(futures).await;
+        let sequences_analysed = fail_fast::collect_in_order(self.sequence_diffs(
+            diff_payload,
+            schema_name.to_owned(),
+            sequences,
+        ))
+        .await;
         let elapsed = start.elapsed();
         debug!(
             "{}",
@@ -90,17 +82,146 @@ impl<SQE: SequenceSingleSourceQueryExecutor, DSQE: SequenceDualSourceQueryExecut
             .bold(),
         );
 
-        for sequence_diff_result in &sequences_analysed {
+        for sequence_diff_result in sequences_analysed
+            .iter()
+            .filter(|output| !diff_payload.only_differences() || output.is_difference())
+        {
             info!("{}", sequence_diff_result.to_string());
         }
 
         Ok(sequences_analysed
             .into_iter()
-            .map(|diff| diff.into())
+            .map(|diff| diff.convert(SchemaName::new(schema_name.to_owned())))
             .collect())
     }
 
-    pub async fn get_all_sequences(&self, schema_name: String) -> Result<Vec<String>> {
+    /// Compares every sequence of the schema, yielding the output of each sequence as soon
+    /// as it is known, in the order the comparisons complete.
+    pub async fn diff_all_sequences_stream<'a>(
+        &'a self,
+        diff_payload: &'a DiffPayload,
+        schema_name: String,
+    ) -> Result<impl Stream<Item = DiffOutput> + 'a> {
+        let sequences = self.get_sorted_sequences(schema_name.to_owned()).await?;
+
+        Ok(self
+            .sequence_diffs(diff_payload, schema_name.to_owned(), sequences)
+            .map(move |(_, diff)| diff.convert(SchemaName::new(schema_name.to_owned()))))
+    }
+
+    async fn get_sorted_sequences(&self, schema_name: String) -> Result<Vec<SequenceName>> {
+        let mut sequences = self.get_all_sequences(schema_name).await?;
+
+        sequences.sort_by_key(|s| s.name().to_lowercase());
+
+        Ok(sequences)
+    }
+
+    /// Compares the sequences concurrently (up to `max_concurrency` at a time), yielding the output of each along with the position of the sequence.
+    fn sequence_diffs<'a>(
+        &'a self,
+        diff_payload: &'a DiffPayload,
+        schema_name: String,
+        sequences: Vec<SequenceName>,
+    ) -> impl Stream<Item = (usize, SequenceDiffOutput)> + 'a {
+        let futures = sequences.into_iter().map(move |sequence_name| {
+            let schema_name = schema_name.to_owned();
+
+            async move {
+                self.diff_sequence(diff_payload, &schema_name, sequence_name)
+                    .await
+                    .with_source_aliases(diff_payload.first_alias(), diff_payload.second_alias())
+            }
+        });
+
+        fail_fast::until_difference(
+            futures,
+            diff_payload
+                .max_concurrency()
+                .map(|max_concurrency| max_concurrency as usize),
+            diff_payload.fail_fast(),
+            SequenceDiffOutput::is_difference,
+        )
+        .take_until(self.cancellation_token.cancelled())
+    }
+
+    async fn diff_sequence(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: &str,
+        sequence_name: SequenceName,
+    ) -> SequenceDiffOutput {
+        let start = Instant::now();
+
+        // Query the sequence within the schema it was found in, rather than assuming the one being diffed
+        let schema_name = sequence_name
+            .schema_name()
+            .cloned()
+            .unwrap_or_else(|| SchemaName::new(schema_name.to_owned()));
+
+        debug!(
+            "{}",
+            format!("Analyzing sequence: {}", &sequence_name.name())
+                .yellow()
+                .bold()
+        );
+
+        // Values of a live system change constantly, so compare only the definition if asked
+        let sequence_diff_result = if diff_payload.sequences_structure_only() {
+            let input = QuerySequenceMetadataInput::new(schema_name, sequence_name.to_owned());
+            let (first_result, second_result) = self
+                .dual_sequence_query_executor
+                .query_sequence_metadata(input)
+                .await;
+
+            Self::extract_structure_result(sequence_name.name(), first_result, second_result)
+        } else {
+            // Compare the definitions along with the values, since they may drift apart while sharing a value
+            let last_values_input =
+                QueryLastValuesInput::new(schema_name.to_owned(), sequence_name.to_owned());
+            let metadata_input =
+                QuerySequenceMetadataInput::new(schema_name, sequence_name.to_owned());
+            let ((first_result, second_result), metadata) = futures::future::join(
+                self.dual_sequence_query_executor
+                    .query_sequence_last_values(last_values_input),
+                self.dual_sequence_query_executor
+                    .query_sequence_metadata(metadata_input),
+            )
+            .await;
+
+            // Definitions that cannot be read (e.g. of a missing sequence) are not compared
+            let (structure_diff, ownership_diff) = match metadata {
+                (Ok(first_metadata), Ok(second_metadata)) => (
+                    Some(SequenceStructureDiff::new(
+                        first_metadata.clone(),
+                        second_metadata.clone(),
+                    ))
+                    .filter(SequenceStructureDiff::has_changes),
+                    Self::ownership_diff(&first_metadata, &second_metadata),
+                ),
+                _ => (None, None),
+            };
+
+            Self::extract_result(
+                sequence_name.name(),
+                first_result,
+                second_result,
+                structure_diff,
+            )
+            .or_ownership_diff(ownership_diff)
+        };
+
+        let elapsed = start.elapsed();
+        debug!(
+            "{}",
+            format!("Sequence analysis completed in: {}ms", elapsed.as_millis())
+        );
+        debug!("##############################################");
+
+        sequence_diff_result
+    }
+
+    pub async fn get_all_sequences(&self, schema_name: String) -> Result<Vec<SequenceName>> {
         let input = QueryAllSequencesInput::new(SchemaName::new(schema_name));
         let query_result = self
             .single_sequence_query_executor
@@ -113,20 +234,72 @@ impl<SQE: SequenceSingleSourceQueryExecutor, DSQE: SequenceDualSourceQueryExecut
         sequence_name: String,
         first_result: Result<i64>,
         second_result: Result<i64>,
+        structure_diff: Option<SequenceStructureDiff>,
     ) -> SequenceDiffOutput {
         match (first_result, second_result) {
             (Ok(first_value), Ok(second_value)) => {
                 if first_value != second_value {
                     SequenceDiffOutput::Diff(
                         sequence_name,
-                        SequenceCountDiff::new(first_value, second_value),
+                        SequenceCountDiff::new(first_value, second_value)
+                            .with_structure_diff(structure_diff),
                     )
+                } else if let Some(structure_diff) = structure_diff {
+                    SequenceDiffOutput::StructureDiff(sequence_name, structure_diff)
                 } else {
                     SequenceDiffOutput::NoDiff(sequence_name)
                 }
             }
-            (Err(_e), _) => SequenceDiffOutput::NotExists(sequence_name, SequenceSource::First),
-            (_, Err(_e)) => SequenceDiffOutput::NotExists(sequence_name, SequenceSource::Second),
+            (Err(e), _) if QueryTimeoutError::is_timeout(&e) => {
+                SequenceDiffOutput::Timeout(sequence_name, SequenceSource::First(None))
+            }
+            (_, Err(e)) if QueryTimeoutError::is_timeout(&e) => {
+                SequenceDiffOutput::Timeout(sequence_name, SequenceSource::Second(None))
+            }
+            (Err(_e), _) => {
+                SequenceDiffOutput::NotExists(sequence_name, SequenceSource::First(None))
+            }
+            (_, Err(_e)) => {
+                SequenceDiffOutput::NotExists(sequence_name, SequenceSource::Second(None))
+            }
+        }
+    }
+
+    /// Returns how the columns owning the sequences differ, if they do.
+    fn ownership_diff(
+        first_metadata: &SequenceMetadata,
+        second_metadata: &SequenceMetadata,
+    ) -> Option<SequenceOwnershipDiff> {
+        Some(SequenceOwnershipDiff::new(
+            first_metadata.owner().cloned(),
+            second_metadata.owner().cloned(),
+        ))
+        .filter(SequenceOwnershipDiff::has_changes)
+    }
+
+    fn extract_structure_result(
+        sequence_name: String,
+        first_result: Result<SequenceMetadata>,
+        second_result: Result<SequenceMetadata>,
+    ) -> SequenceDiffOutput {
+        match (first_result, second_result) {
+            (Ok(first_metadata), Ok(second_metadata)) => {
+                let ownership_diff = Self::ownership_diff(&first_metadata, &second_metadata);
+                let structure_diff = SequenceStructureDiff::new(first_metadata, second_metadata);
+                if structure_diff.has_changes() {
+                    SequenceDiffOutput::StructureDiff(sequence_name, structure_diff)
+                } else if let Some(ownership_diff) = ownership_diff {
+                    SequenceDiffOutput::OwnershipDiff(sequence_name, ownership_diff)
+                } else {
+                    SequenceDiffOutput::NoDiff(sequence_name)
+                }
+            }
+            (Err(_e), _) => {
+                SequenceDiffOutput::NotExists(sequence_name, SequenceSource::First(None))
+            }
+            (_, Err(_e)) => {
+                SequenceDiffOutput::NotExists(sequence_name, SequenceSource::Second(None))
+            }
         }
     }
 }
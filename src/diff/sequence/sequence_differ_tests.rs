@@ -1,11 +1,37 @@
 #[cfg(test)]
 mod tests {
     use crate::diff::diff_output::DiffOutput;
+    use crate::diff::diff_payload::DiffPayload;
     use crate::diff::sequence::query::output::SequenceDiffOutput;
     use crate::diff::sequence::query::sequence_query_executor::{
         MockSequenceDualSourceQueryExecutor, MockSequenceSingleSourceQueryExecutor,
     };
+    use crate::diff::sequence::query::sequence_types::{
+        SequenceMetadata, SequenceName, SequenceOwner,
+    };
     use crate::diff::sequence::sequence_differ::SequenceDiffer;
+    use crate::diff::types::SchemaName;
+
+    const EMPTY_STRING_VEC: Vec<String> = Vec::new();
+
+    fn diff_payload(sequences_structure_only: bool) -> DiffPayload {
+        DiffPayload::builder()
+            .first_db("first_db")
+            .second_db("second_db")
+            .only_tables(false)
+            .only_sequences(true)
+            .only_count(false)
+            .chunk_size(10000)
+            .start_position(0)
+            .max_connections(10)
+            .include_tables(EMPTY_STRING_VEC)
+            .exclude_tables(EMPTY_STRING_VEC)
+            .schema_name("public")
+            .accept_invalid_certs_first_db(false)
+            .accept_invalid_certs_second_db(false)
+            .sequences_structure_only(sequences_structure_only)
+            .build()
+    }
 
     #[tokio::test]
     async fn test_get_all_sequences() {
@@ -15,7 +41,12 @@ mod tests {
         single_source_query_executor
             .expect_query_sequence_names()
             .times(1)
-            .returning(|_| vec!["sequence1".to_string(), "sequence2".to_string()]);
+            .returning(|_| {
+                vec![
+                    SequenceName::new("sequence1"),
+                    SequenceName::new("sequence2"),
+                ]
+            });
 
         let sequence_differ =
             SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
@@ -26,8 +57,8 @@ mod tests {
             .unwrap();
 
         assert_eq!(sequences.len(), 2);
-        assert_eq!(sequences[0], "sequence1");
-        assert_eq!(sequences[1], "sequence2");
+        assert_eq!(sequences[0].name(), "sequence1");
+        assert_eq!(sequences[1].name(), "sequence2");
     }
 
     #[tokio::test]
@@ -38,34 +69,365 @@ mod tests {
         single_source_query_executor
             .expect_query_sequence_names()
             .times(1)
-            .returning(|_| vec!["sequence1".to_string()]);
+            .returning(|_| vec![SequenceName::new("sequence1")]);
 
         dual_source_query_executor
             .expect_query_sequence_last_values()
             .times(1)
             .returning(|_| (Ok(2), Ok(1)));
 
+        dual_source_query_executor
+            .expect_query_sequence_metadata()
+            .times(1)
+            .returning(|_| {
+                (
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                )
+            });
+
         let sequence_differ =
             SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
 
         let sequences = sequence_differ
-            .diff_all_sequences("public".to_string())
+            .diff_all_sequences(&diff_payload(false), "public".to_string())
             .await
             .unwrap();
         let actual = sequences.first().unwrap();
 
         assert_eq!(sequences.len(), 1);
-        assert!(matches!(actual, DiffOutput::SequenceDiff(_)));
+        assert!(matches!(actual, DiffOutput::SequenceDiff(_, _)));
         match actual {
-            DiffOutput::SequenceDiff(sequence_diff_output) => match sequence_diff_output {
-                SequenceDiffOutput::Diff(sequence_name, sequence_count_diff) => {
-                    assert_eq!("sequence1", sequence_name);
-                    assert_eq!(1, sequence_count_diff.second());
-                    assert_eq!(2, sequence_count_diff.first());
+            DiffOutput::SequenceDiff(schema_name, sequence_diff_output) => {
+                match sequence_diff_output {
+                    SequenceDiffOutput::Diff(sequence_name, sequence_count_diff) => {
+                        assert_eq!("public", schema_name.name());
+                        assert_eq!("sequence1", sequence_name);
+                        assert_eq!(1, sequence_count_diff.second());
+                        assert_eq!(2, sequence_count_diff.first());
+                        assert!(sequence_count_diff.structure_diff().is_none());
+                    }
+                    _ => panic!("Expected Diff"),
                 }
-                _ => panic!("Expected Diff"),
-            },
+            }
             _ => panic!("Expected SequenceDiff"),
         }
     }
+
+    #[tokio::test]
+    async fn test_diff_all_sequences_structure_only_ignores_values() {
+        let mut single_source_query_executor = MockSequenceSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockSequenceDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_sequence_names()
+            .times(1)
+            .returning(|_| vec![SequenceName::new("sequence1")]);
+
+        dual_source_query_executor
+            .expect_query_sequence_last_values()
+            .never();
+
+        dual_source_query_executor
+            .expect_query_sequence_metadata()
+            .times(1)
+            .returning(|_| {
+                (
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                )
+            });
+
+        let sequence_differ =
+            SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let sequences = sequence_differ
+            .diff_all_sequences(&diff_payload(true), "public".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(sequences.len(), 1);
+        match sequences.first().unwrap() {
+            DiffOutput::SequenceDiff(_, SequenceDiffOutput::NoDiff(sequence_name)) => {
+                assert_eq!("sequence1", sequence_name);
+            }
+            _ => panic!("Expected NoDiff"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_sequences_structure_only_reports_definition_changes() {
+        let mut single_source_query_executor = MockSequenceSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockSequenceDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_sequence_names()
+            .times(1)
+            .returning(|_| vec![SequenceName::new("sequence1")]);
+
+        dual_source_query_executor
+            .expect_query_sequence_metadata()
+            .times(1)
+            .returning(|_| {
+                (
+                    Ok(SequenceMetadata::new("integer", 1, 1, 2147483647, 1, false)),
+                    Ok(SequenceMetadata::new("bigint", 5, 1, 2147483647, 1, false)),
+                )
+            });
+
+        let sequence_differ =
+            SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let sequences = sequence_differ
+            .diff_all_sequences(&diff_payload(true), "public".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(sequences.len(), 1);
+        match sequences.first().unwrap() {
+            DiffOutput::SequenceDiff(_, SequenceDiffOutput::StructureDiff(sequence_name, diff)) => {
+                assert_eq!("sequence1", sequence_name);
+                assert_eq!(
+                    vec!["type: integer -> bigint", "increment: 1 -> 5"],
+                    diff.changes()
+                );
+            }
+            _ => panic!("Expected StructureDiff"),
+        }
+    }
+    #[tokio::test]
+    async fn test_diff_all_sequences_structure_only_reports_cache_and_cycle_changes() {
+        let mut single_source_query_executor = MockSequenceSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockSequenceDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_sequence_names()
+            .times(1)
+            .returning(|_| vec![SequenceName::new("sequence1")]);
+
+        dual_source_query_executor
+            .expect_query_sequence_metadata()
+            .times(1)
+            .returning(|_| {
+                (
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 20, true)),
+                )
+            });
+
+        let sequence_differ =
+            SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let sequences = sequence_differ
+            .diff_all_sequences(&diff_payload(true), "public".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(sequences.len(), 1);
+        match sequences.first().unwrap() {
+            DiffOutput::SequenceDiff(_, SequenceDiffOutput::StructureDiff(sequence_name, diff)) => {
+                assert_eq!("sequence1", sequence_name);
+                assert_eq!(
+                    vec!["cache: 1 -> 20", "cycle: false -> true"],
+                    diff.changes()
+                );
+            }
+            _ => panic!("Expected StructureDiff"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_sequences_reports_definition_changes_of_equal_values() {
+        let mut single_source_query_executor = MockSequenceSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockSequenceDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_sequence_names()
+            .times(1)
+            .returning(|_| vec![SequenceName::new("sequence1")]);
+
+        dual_source_query_executor
+            .expect_query_sequence_last_values()
+            .times(1)
+            .returning(|_| (Ok(10), Ok(10)));
+
+        dual_source_query_executor
+            .expect_query_sequence_metadata()
+            .times(1)
+            .returning(|_| {
+                (
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                    Ok(SequenceMetadata::new("bigint", 2, 1, 1000, 1, false)),
+                )
+            });
+
+        let sequence_differ =
+            SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let sequences = sequence_differ
+            .diff_all_sequences(&diff_payload(false), "public".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(sequences.len(), 1);
+        match sequences.first().unwrap() {
+            DiffOutput::SequenceDiff(_, SequenceDiffOutput::StructureDiff(sequence_name, diff)) => {
+                assert_eq!("sequence1", sequence_name);
+                assert_eq!(
+                    vec![
+                        "increment: 1 -> 2".to_string(),
+                        format!("max: {} -> 1000", i64::MAX)
+                    ],
+                    diff.changes()
+                );
+            }
+            _ => panic!("Expected StructureDiff"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_sequences_reports_definition_changes_along_with_values() {
+        let mut single_source_query_executor = MockSequenceSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockSequenceDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_sequence_names()
+            .times(1)
+            .returning(|_| vec![SequenceName::new("sequence1")]);
+
+        dual_source_query_executor
+            .expect_query_sequence_last_values()
+            .times(1)
+            .returning(|_| (Ok(2), Ok(1)));
+
+        dual_source_query_executor
+            .expect_query_sequence_metadata()
+            .times(1)
+            .returning(|_| {
+                (
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, true)),
+                )
+            });
+
+        let sequence_differ =
+            SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let sequences = sequence_differ
+            .diff_all_sequences(&diff_payload(false), "public".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(sequences.len(), 1);
+        match sequences.first().unwrap() {
+            DiffOutput::SequenceDiff(
+                _,
+                sequence_diff @ SequenceDiffOutput::Diff(_, count_diff),
+            ) => {
+                assert_eq!(
+                    vec!["cycle: false -> true"],
+                    count_diff.structure_diff().unwrap().changes()
+                );
+                assert_eq!(
+                    "Difference in sequence:sequence1 - First: 2, Second: 1, cycle: false -> true\n",
+                    &*sequence_diff.to_string()
+                );
+            }
+            _ => panic!("Expected Diff"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_sequences_queries_the_schema_of_each_sequence() {
+        let mut single_source_query_executor = MockSequenceSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockSequenceDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_sequence_names()
+            .times(1)
+            .returning(|_| {
+                vec![SequenceName::qualified(
+                    SchemaName::new("reporting"),
+                    "sequence1",
+                )]
+            });
+
+        dual_source_query_executor
+            .expect_query_sequence_last_values()
+            .withf(|input| input.schema_name().name() == "reporting")
+            .times(1)
+            .returning(|_| (Ok(1), Ok(1)));
+
+        dual_source_query_executor
+            .expect_query_sequence_metadata()
+            .withf(|input| input.schema_name().name() == "reporting")
+            .times(1)
+            .returning(|_| {
+                (
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                    Ok(SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false)),
+                )
+            });
+
+        let sequence_differ =
+            SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let sequences = sequence_differ
+            .diff_all_sequences(&diff_payload(false), "public".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(sequences.len(), 1);
+        assert!(!sequences[0].is_difference());
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_sequences_reports_ownership_differences() {
+        let mut single_source_query_executor = MockSequenceSingleSourceQueryExecutor::new();
+        let mut dual_source_query_executor = MockSequenceDualSourceQueryExecutor::new();
+
+        single_source_query_executor
+            .expect_query_sequence_names()
+            .times(1)
+            .returning(|_| vec![SequenceName::new("orders_id_seq")]);
+
+        dual_source_query_executor
+            .expect_query_sequence_last_values()
+            .times(1)
+            .returning(|_| (Ok(5), Ok(5)));
+
+        // The sequence was detached from its column in the second database, e.g. by a restore
+        dual_source_query_executor
+            .expect_query_sequence_metadata()
+            .times(1)
+            .returning(|_| {
+                let metadata = SequenceMetadata::new("bigint", 1, 1, i64::MAX, 1, false);
+                (
+                    Ok(metadata
+                        .clone()
+                        .with_owner(Some(SequenceOwner::new("orders", "id", false)))),
+                    Ok(metadata),
+                )
+            });
+
+        let sequence_differ =
+            SequenceDiffer::new(single_source_query_executor, dual_source_query_executor);
+
+        let sequences = sequence_differ
+            .diff_all_sequences(&diff_payload(false), "public".to_string())
+            .await
+            .unwrap();
+
+        match sequences.first().unwrap() {
+            DiffOutput::SequenceDiff(
+                _,
+                SequenceDiffOutput::OwnershipDiff(name, ownership_diff),
+            ) => {
+                assert_eq!("orders_id_seq", name);
+                assert_eq!("orders", ownership_diff.first().unwrap().table_name());
+                assert!(ownership_diff.second().is_none());
+            }
+            _ => panic!("Expected OwnershipDiff"),
+        }
+    }
 }
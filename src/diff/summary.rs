@@ -0,0 +1,250 @@
+use std::time::Duration;
+
+use colored::{ColoredString, Colorize};
+use serde::Serialize;
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::sequence::query::output::SequenceDiffOutput;
+use crate::diff::table::query::output::TableDiffOutput;
+
+/// Aggregate statistics of a diff run, folded from its results.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DiffSummary {
+    tables_identical: usize,
+    tables_with_count_diff: usize,
+    tables_with_data_diff: usize,
+    tables_missing: usize,
+    tables_without_primary_key: usize,
+    tables_failed: usize,
+    sequences_identical: usize,
+    sequences_different: usize,
+    sequences_missing: usize,
+    sequences_failed: usize,
+    duration_ms: u128,
+}
+
+impl DiffSummary {
+    /// Folds the results of a run into a summary, along with the wall-clock time of the run.
+    ///
+    /// Tables that are empty on one side are counted as count differences, while queries
+    /// that timed out or failed are counted as failures. Column definition and trigger results
+    /// are not counted.
+    pub fn new(diff_output: &[DiffOutput], duration: Duration) -> Self {
+        let mut summary = diff_output
+            .iter()
+            .fold(Self::default(), |mut summary, diff_output| {
+                match diff_output {
+                    DiffOutput::TableDiff(_, table_diff) => match table_diff.relation_output() {
+                        TableDiffOutput::NoCountDiff(_, _)
+                        | TableDiffOutput::NoDiffWithDuration(_, _) => {
+                            summary.tables_identical += 1
+                        }
+                        TableDiffOutput::EstimatedCount(_, first, second) if first == second => {
+                            summary.tables_identical += 1
+                        }
+                        TableDiffOutput::Diff(_, _)
+                        | TableDiffOutput::EmptyOnOneSide(_, _, _)
+                        | TableDiffOutput::EstimatedCount(_, _, _) => {
+                            summary.tables_with_count_diff += 1
+                        }
+                        TableDiffOutput::DataDiffWithDuration(_, _, _, _) => {
+                            summary.tables_with_data_diff += 1
+                        }
+                        TableDiffOutput::NotExists(_, _) => summary.tables_missing += 1,
+                        TableDiffOutput::NoPrimaryKeyFound(_) => {
+                            summary.tables_without_primary_key += 1
+                        }
+                        TableDiffOutput::Timeout(_, _) | TableDiffOutput::Error(_, _) => {
+                            summary.tables_failed += 1
+                        }
+                        TableDiffOutput::View(_) | TableDiffOutput::Sampled(_, _) => {
+                            unreachable!("Views and samples are unwrapped")
+                        }
+                    },
+                    DiffOutput::SequenceDiff(_, sequence_diff) => match sequence_diff {
+                        SequenceDiffOutput::NoDiff(_) => summary.sequences_identical += 1,
+                        SequenceDiffOutput::Diff(_, _)
+                        | SequenceDiffOutput::StructureDiff(_, _)
+                        | SequenceDiffOutput::OwnershipDiff(_, _) => {
+                            summary.sequences_different += 1
+                        }
+                        SequenceDiffOutput::NotExists(_, _) => summary.sequences_missing += 1,
+                        SequenceDiffOutput::Timeout(_, _) => summary.sequences_failed += 1,
+                    },
+                    DiffOutput::SchemaDiff(_, _) | DiffOutput::TriggerDiff(_, _) => {}
+                }
+                summary
+            });
+
+        summary.duration_ms = duration.as_millis();
+        summary
+    }
+
+    pub fn tables_identical(&self) -> usize {
+        self.tables_identical
+    }
+    pub fn tables_with_count_diff(&self) -> usize {
+        self.tables_with_count_diff
+    }
+    pub fn tables_with_data_diff(&self) -> usize {
+        self.tables_with_data_diff
+    }
+    pub fn tables_missing(&self) -> usize {
+        self.tables_missing
+    }
+    pub fn tables_without_primary_key(&self) -> usize {
+        self.tables_without_primary_key
+    }
+    pub fn tables_failed(&self) -> usize {
+        self.tables_failed
+    }
+    pub fn sequences_identical(&self) -> usize {
+        self.sequences_identical
+    }
+    pub fn sequences_different(&self) -> usize {
+        self.sequences_different
+    }
+    pub fn sequences_missing(&self) -> usize {
+        self.sequences_missing
+    }
+    pub fn sequences_failed(&self) -> usize {
+        self.sequences_failed
+    }
+    pub fn duration_ms(&self) -> u128 {
+        self.duration_ms
+    }
+
+    /// Determines whether any table or sequence differs, is missing or could not be compared.
+    pub fn has_differences(&self) -> bool {
+        self.tables_with_count_diff
+            + self.tables_with_data_diff
+            + self.tables_missing
+            + self.tables_without_primary_key
+            + self.tables_failed
+            + self.sequences_different
+            + self.sequences_missing
+            + self.sequences_failed
+            > 0
+    }
+
+    /// Converts the summary to a colored block: green when nothing differs, red otherwise.
+    pub fn to_string(&self) -> ColoredString {
+        let summary = format!(
+            "Summary\n\
+             Tables: {} identical, {} with count diff, {} with data diff, {} missing, {} without primary key, {} failed\n\
+             Sequences: {} identical, {} different, {} missing, {} failed\n\
+             Total time: {}ms",
+            self.tables_identical,
+            self.tables_with_count_diff,
+            self.tables_with_data_diff,
+            self.tables_missing,
+            self.tables_without_primary_key,
+            self.tables_failed,
+            self.sequences_identical,
+            self.sequences_different,
+            self.sequences_missing,
+            self.sequences_failed,
+            self.duration_ms
+        );
+
+        if self.has_differences() {
+            summary.red().bold()
+        } else {
+            summary.green().bold()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::sequence::query::output::{SequenceCountDiff, SequenceSource};
+    use crate::diff::table::query::output::{TableCountDiff, TableSource, TableTimings};
+    use crate::diff::types::SchemaName;
+
+    fn table_diff(table_diff: TableDiffOutput) -> DiffOutput {
+        DiffOutput::TableDiff(SchemaName::new("public"), table_diff)
+    }
+
+    fn sequence_diff(sequence_diff: SequenceDiffOutput) -> DiffOutput {
+        DiffOutput::SequenceDiff(SchemaName::new("public"), sequence_diff)
+    }
+
+    #[test]
+    fn test_summary_counts_results() {
+        let diff_output = vec![
+            table_diff(TableDiffOutput::NoCountDiff("table1".to_string(), 10)),
+            table_diff(TableDiffOutput::NoDiffWithDuration(
+                "table2".to_string(),
+                TableTimings::new(Duration::ZERO, Duration::from_millis(1)),
+            )),
+            table_diff(TableDiffOutput::Diff(
+                "table3".to_string(),
+                TableCountDiff::new(2, 1),
+            )),
+            table_diff(TableDiffOutput::EmptyOnOneSide(
+                "table4".to_string(),
+                TableSource::First(None),
+                3,
+            )),
+            table_diff(TableDiffOutput::DataDiffWithDuration(
+                "table5".to_string(),
+                0,
+                100,
+                TableTimings::new(Duration::ZERO, Duration::from_millis(1)),
+            )),
+            table_diff(TableDiffOutput::NotExists(
+                "table6".to_string(),
+                TableSource::Second(None),
+            )),
+            table_diff(TableDiffOutput::NoPrimaryKeyFound("table7".to_string())),
+            table_diff(TableDiffOutput::Timeout(
+                "table8".to_string(),
+                TableSource::First(None),
+            )),
+            sequence_diff(SequenceDiffOutput::NoDiff("sequence1".to_string())),
+            sequence_diff(SequenceDiffOutput::Diff(
+                "sequence2".to_string(),
+                SequenceCountDiff::new(2, 1),
+            )),
+            sequence_diff(SequenceDiffOutput::NotExists(
+                "sequence3".to_string(),
+                SequenceSource::First(None),
+            )),
+        ];
+
+        let summary = DiffSummary::new(&diff_output, Duration::from_millis(1500));
+
+        assert_eq!(summary.tables_identical(), 2);
+        assert_eq!(summary.tables_with_count_diff(), 2);
+        assert_eq!(summary.tables_with_data_diff(), 1);
+        assert_eq!(summary.tables_missing(), 1);
+        assert_eq!(summary.tables_without_primary_key(), 1);
+        assert_eq!(summary.tables_failed(), 1);
+        assert_eq!(summary.sequences_identical(), 1);
+        assert_eq!(summary.sequences_different(), 1);
+        assert_eq!(summary.sequences_missing(), 1);
+        assert_eq!(summary.sequences_failed(), 0);
+        assert_eq!(summary.duration_ms(), 1500);
+        assert!(summary.has_differences());
+    }
+
+    #[test]
+    fn test_summary_without_differences() {
+        let diff_output = vec![
+            table_diff(TableDiffOutput::NoCountDiff("table1".to_string(), 10)),
+            sequence_diff(SequenceDiffOutput::NoDiff("sequence1".to_string())),
+        ];
+
+        let summary = DiffSummary::new(&diff_output, Duration::from_millis(5));
+
+        assert!(!summary.has_differences());
+        assert_eq!(
+            &*summary.to_string(),
+            "Summary\n\
+             Tables: 1 identical, 0 with count diff, 0 with data diff, 0 missing, 0 without primary key, 0 failed\n\
+             Sequences: 1 identical, 0 different, 0 missing, 0 failed\n\
+             Total time: 5ms"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod query;
 pub mod table_differ;
+pub mod table_order;
 
 #[cfg(test)]
 mod table_differ_tests;
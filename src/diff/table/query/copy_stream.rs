@@ -0,0 +1,129 @@
+use anyhow::Result;
+use futures::{Stream, StreamExt};
+
+/// Reads the rows of a `COPY ... TO STDOUT` stream in text format, one at a time.
+///
+/// The text format escapes newlines within values, so every newline terminates a row,
+/// regardless of how the rows are split across the chunks of the stream.
+pub struct CopyRowReader<S> {
+    stream: S,
+    buffer: Vec<u8>,
+}
+
+impl<S, B, E> CopyRowReader<S>
+where
+    S: Stream<Item = std::result::Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+    E: Into<anyhow::Error>,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Returns the next row, without its trailing newline, or `None` at the end of the stream.
+    pub async fn next_row(&mut self) -> Result<Option<Vec<u8>>> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+                let rest = self.buffer.split_off(end + 1);
+                let mut row = std::mem::replace(&mut self.buffer, rest);
+                row.pop();
+                return Ok(Some(row));
+            }
+
+            match self.stream.next().await {
+                Some(chunk) => self
+                    .buffer
+                    .extend_from_slice(chunk.map_err(Into::<anyhow::Error>::into)?.as_ref()),
+                // A last row without a trailing newline is still a row
+                None if self.buffer.is_empty() => return Ok(None),
+                None => return Ok(Some(std::mem::take(&mut self.buffer))),
+            }
+        }
+    }
+}
+
+/// Compares two `COPY` streams row by row, as the rows arrive.
+///
+/// Returns the position of the first row that differs, or that exists in only one of the
+/// streams, and `None` when both streams hold the same rows.
+pub async fn first_difference<S1, S2, B1, B2, E1, E2>(first: S1, second: S2) -> Result<Option<i64>>
+where
+    S1: Stream<Item = std::result::Result<B1, E1>> + Unpin,
+    S2: Stream<Item = std::result::Result<B2, E2>> + Unpin,
+    B1: AsRef<[u8]>,
+    B2: AsRef<[u8]>,
+    E1: Into<anyhow::Error>,
+    E2: Into<anyhow::Error>,
+{
+    let mut first = CopyRowReader::new(first);
+    let mut second = CopyRowReader::new(second);
+
+    let mut position = 0;
+    loop {
+        let (first_row, second_row) =
+            futures::future::try_join(first.next_row(), second.next_row()).await?;
+
+        match (first_row, second_row) {
+            (None, None) => return Ok(None),
+            (first_row, second_row) if first_row == second_row => position += 1,
+            _ => return Ok(Some(position)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+
+    fn copy_stream(chunks: Vec<&'static str>) -> impl Stream<Item = Result<&'static [u8]>> + Unpin {
+        stream::iter(chunks.into_iter().map(|chunk| Ok(chunk.as_bytes())))
+    }
+
+    #[tokio::test]
+    async fn test_read_rows_split_across_chunks() {
+        let mut reader = CopyRowReader::new(copy_stream(vec!["1\ta\n2\t", "b\n", "3\tc"]));
+
+        assert_eq!(reader.next_row().await.unwrap(), Some(b"1\ta".to_vec()));
+        assert_eq!(reader.next_row().await.unwrap(), Some(b"2\tb".to_vec()));
+        assert_eq!(reader.next_row().await.unwrap(), Some(b"3\tc".to_vec()));
+        assert_eq!(reader.next_row().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_same_rows_in_different_chunks() {
+        let first = copy_stream(vec!["1\ta\n2\tb\n"]);
+        let second = copy_stream(vec!["1\t", "a\n2", "\tb\n"]);
+
+        assert_eq!(first_difference(first, second).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_first_differing_row() {
+        let first = copy_stream(vec!["1\ta\n2\tb\n3\tc\n"]);
+        let second = copy_stream(vec!["1\ta\n2\tx\n3\tc\n"]);
+
+        assert_eq!(first_difference(first, second).await.unwrap(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_row_missing_from_one_stream() {
+        let first = copy_stream(vec!["1\ta\n2\tb\n"]);
+        let second = copy_stream(vec!["1\ta\n"]);
+
+        assert_eq!(first_difference(first, second).await.unwrap(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_stream_error() {
+        let first = copy_stream(vec!["1\ta\n"]);
+        let second = stream::iter(vec![Err::<&[u8], _>(anyhow::anyhow!("connection closed"))]);
+
+        let error = first_difference(first, second).await.unwrap_err();
+
+        assert_eq!(error.to_string(), "connection closed");
+    }
+}
@@ -1,21 +1,190 @@
-use super::table_types::{TableName, TableOffset, TablePosition, TablePrimaryKeys};
+use super::table_types::{
+    TableFilter, TableHashExpression, TableName, TableOffset, TablePosition, TablePrimaryKeys,
+    TableProjection, TableSample,
+};
 use crate::diff::types::SchemaName;
 
 /// Represents the input for querying the count of a table.
 pub struct QueryTableCountInput {
     schema_name: SchemaName,
     table_name: TableName,
+    second_table_name: Option<TableName>,
+    first_filter: TableFilter,
+    second_filter: TableFilter,
+    estimate: bool,
+    sample: TableSample,
 }
 
 impl QueryTableCountInput {
     /// Creates a new `QueryTableCountInput` instance.
-    pub fn new(schema_name: SchemaName, table_name: TableName) -> Self {
+    ///
+    /// The filters are applied to the first and the second database respectively.
+    pub fn new(
+        schema_name: SchemaName,
+        table_name: TableName,
+        first_filter: TableFilter,
+        second_filter: TableFilter,
+    ) -> Self {
+        Self {
+            schema_name,
+            table_name,
+            second_table_name: None,
+            first_filter,
+            second_filter,
+            estimate: false,
+            sample: TableSample::default(),
+        }
+    }
+
+    /// Sets the name of the table in the second database, when it differs from its name in the first.
+    pub fn with_second_table_name(mut self, second_table_name: TableName) -> Self {
+        self.second_table_name = Some(second_table_name);
+        self
+    }
+
+    /// Sets whether to read approximate counts from the planner statistics, ignoring the filters.
+    pub fn with_estimate(mut self, estimate: bool) -> Self {
+        self.estimate = estimate;
+        self
+    }
+
+    /// Restricts the count to the rows of the pages sampled in both databases.
+    pub fn with_sample(mut self, sample: TableSample) -> Self {
+        self.sample = sample;
+        self
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
+    }
+
+    pub fn table_name(&self) -> &TableName {
+        &self.table_name
+    }
+
+    /// Returns the name of the table in the second database, which defaults to its name in the first.
+    pub fn second_table_name(&self) -> &TableName {
+        self.second_table_name.as_ref().unwrap_or(&self.table_name)
+    }
+
+    pub fn first_filter(&self) -> &TableFilter {
+        &self.first_filter
+    }
+
+    pub fn second_filter(&self) -> &TableFilter {
+        &self.second_filter
+    }
+
+    pub fn estimate(&self) -> bool {
+        self.estimate
+    }
+
+    pub fn sample(&self) -> &TableSample {
+        &self.sample
+    }
+}
+
+/// Represents the input for querying the range of the key of a table.
+pub struct QueryKeyRangeInput {
+    schema_name: SchemaName,
+    table_name: TableName,
+    second_table_name: Option<TableName>,
+    key: TablePrimaryKeys,
+    first_filter: TableFilter,
+    second_filter: TableFilter,
+}
+
+impl QueryKeyRangeInput {
+    /// Creates a new `QueryKeyRangeInput` instance.
+    ///
+    /// The filters are applied to the first and the second database respectively.
+    pub fn new(
+        schema_name: SchemaName,
+        table_name: TableName,
+        key: TablePrimaryKeys,
+        first_filter: TableFilter,
+        second_filter: TableFilter,
+    ) -> Self {
+        Self {
+            schema_name,
+            table_name,
+            second_table_name: None,
+            key,
+            first_filter,
+            second_filter,
+        }
+    }
+
+    /// Sets the name of the table in the second database, when it differs from its name in the first.
+    pub fn with_second_table_name(mut self, second_table_name: TableName) -> Self {
+        self.second_table_name = Some(second_table_name);
+        self
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
+    }
+
+    pub fn table_name(&self) -> &TableName {
+        &self.table_name
+    }
+
+    /// Returns the name of the table in the second database, which defaults to its name in the first.
+    pub fn second_table_name(&self) -> &TableName {
+        self.second_table_name.as_ref().unwrap_or(&self.table_name)
+    }
+
+    pub fn key(&self) -> &TablePrimaryKeys {
+        &self.key
+    }
+
+    pub fn first_filter(&self) -> &TableFilter {
+        &self.first_filter
+    }
+
+    pub fn second_filter(&self) -> &TableFilter {
+        &self.second_filter
+    }
+}
+
+/// Represents the input for querying the keys of the row at an offset of the first table,
+/// after which the next chunk of rows starts.
+pub struct QueryKeyCursorInput {
+    schema_name: SchemaName,
+    table_name: TableName,
+    primary_keys: TablePrimaryKeys,
+    offset: TableOffset,
+    filter: TableFilter,
+    sample: TableSample,
+}
+
+impl QueryKeyCursorInput {
+    /// Creates a new `QueryKeyCursorInput` instance.
+    ///
+    /// The filter is the one applied to the first database, which the cursor is read from.
+    pub fn new(
+        schema_name: SchemaName,
+        table_name: TableName,
+        primary_keys: TablePrimaryKeys,
+        offset: TableOffset,
+        filter: TableFilter,
+    ) -> Self {
         Self {
             schema_name,
             table_name,
+            primary_keys,
+            offset,
+            filter,
+            sample: TableSample::default(),
         }
     }
 
+    /// Restricts the rows to those of the pages sampled in both databases.
+    pub fn with_sample(mut self, sample: TableSample) -> Self {
+        self.sample = sample;
+        self
+    }
+
     pub fn schema_name(&self) -> &SchemaName {
         &self.schema_name
     }
@@ -23,6 +192,92 @@ impl QueryTableCountInput {
     pub fn table_name(&self) -> &TableName {
         &self.table_name
     }
+
+    pub fn primary_keys(&self) -> &TablePrimaryKeys {
+        &self.primary_keys
+    }
+
+    pub fn offset(&self) -> &TableOffset {
+        &self.offset
+    }
+
+    pub fn filter(&self) -> &TableFilter {
+        &self.filter
+    }
+
+    pub fn sample(&self) -> &TableSample {
+        &self.sample
+    }
+}
+
+/// Represents the input for streaming the rows of a table through `COPY`.
+pub struct QueryCopyRowsInput {
+    schema_name: SchemaName,
+    table_name: TableName,
+    second_table_name: Option<TableName>,
+    projection: TableProjection,
+    primary_keys: TablePrimaryKeys,
+    first_filter: TableFilter,
+    second_filter: TableFilter,
+}
+
+impl QueryCopyRowsInput {
+    /// Creates a new `QueryCopyRowsInput` instance.
+    ///
+    /// The filters are applied to the first and the second database respectively.
+    pub fn new(
+        schema_name: SchemaName,
+        table_name: TableName,
+        projection: TableProjection,
+        primary_keys: TablePrimaryKeys,
+        first_filter: TableFilter,
+        second_filter: TableFilter,
+    ) -> Self {
+        Self {
+            schema_name,
+            table_name,
+            second_table_name: None,
+            projection,
+            primary_keys,
+            first_filter,
+            second_filter,
+        }
+    }
+
+    /// Sets the name of the table in the second database, when it differs from its name in the first.
+    pub fn with_second_table_name(mut self, second_table_name: TableName) -> Self {
+        self.second_table_name = Some(second_table_name);
+        self
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
+    }
+
+    pub fn table_name(&self) -> &TableName {
+        &self.table_name
+    }
+
+    /// Returns the name of the table in the second database, which defaults to its name in the first.
+    pub fn second_table_name(&self) -> &TableName {
+        self.second_table_name.as_ref().unwrap_or(&self.table_name)
+    }
+
+    pub fn projection(&self) -> &TableProjection {
+        &self.projection
+    }
+
+    pub fn primary_keys(&self) -> &TablePrimaryKeys {
+        &self.primary_keys
+    }
+
+    pub fn first_filter(&self) -> &TableFilter {
+        &self.first_filter
+    }
+
+    pub fn second_filter(&self) -> &TableFilter {
+        &self.second_filter
+    }
 }
 
 /// Represents the input for querying table names.
@@ -30,6 +285,8 @@ pub struct QueryTableNamesInput {
     schema_name: SchemaName,
     included_tables: Vec<String>,
     excluded_tables: Vec<String>,
+    include_views: bool,
+    table_types: Vec<String>,
 }
 
 impl QueryTableNamesInput {
@@ -43,9 +300,24 @@ impl QueryTableNamesInput {
             schema_name,
             included_tables: included_tables.into_iter().map(|t| t.into()).collect(),
             excluded_tables: excluded_tables.into_iter().map(|t| t.into()).collect(),
+            include_views: false,
+            table_types: Vec::new(),
         }
     }
 
+    /// Lists the tables of the given types (e.g. `FOREIGN`) along with the base tables.
+    pub fn with_table_types(mut self, table_types: Vec<String>) -> Self {
+        self.table_types = table_types;
+        self
+    }
+
+    /// Also lists the views and materialized views of the schema, where tables and views are listed at once
+    /// (i.e. the names of the second database).
+    pub fn with_views(mut self, include_views: bool) -> Self {
+        self.include_views = include_views;
+        self
+    }
+
     pub fn schema_name(&self) -> &SchemaName {
         &self.schema_name
     }
@@ -57,35 +329,75 @@ impl QueryTableNamesInput {
     pub fn excluded_tables(&self) -> Vec<String> {
         self.excluded_tables.to_vec()
     }
+
+    pub fn include_views(&self) -> bool {
+        self.include_views
+    }
+
+    pub fn table_types(&self) -> Vec<String> {
+        self.table_types.to_vec()
+    }
 }
 
 /// Represents the input for querying hash data.
 pub struct QueryHashDataInput {
     schema_name: SchemaName,
     table_name: TableName,
+    second_table_name: Option<TableName>,
+    projection: TableProjection,
+    hash_expression: TableHashExpression,
     primary_keys: TablePrimaryKeys,
     position: TablePosition,
     offset: TableOffset,
+    first_filter: TableFilter,
+    second_filter: TableFilter,
+    sample: TableSample,
 }
 
 impl QueryHashDataInput {
     /// Creates a new `QueryHashDataInput` instance.
+    ///
+    /// The filters are applied to the first and the second database respectively,
+    /// while the hash expression is applied identically to both.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         schema_name: SchemaName,
         table_name: TableName,
+        projection: TableProjection,
+        hash_expression: TableHashExpression,
         primary_keys: TablePrimaryKeys,
         position: TablePosition,
         offset: TableOffset,
+        first_filter: TableFilter,
+        second_filter: TableFilter,
     ) -> Self {
         Self {
             schema_name,
             table_name,
+            second_table_name: None,
+            projection,
+            hash_expression,
             primary_keys,
             position,
             offset,
+            first_filter,
+            second_filter,
+            sample: TableSample::default(),
         }
     }
 
+    /// Sets the name of the table in the second database, when it differs from its name in the first.
+    pub fn with_second_table_name(mut self, second_table_name: TableName) -> Self {
+        self.second_table_name = Some(second_table_name);
+        self
+    }
+
+    /// Restricts the hashed rows to those of the pages sampled in both databases.
+    pub fn with_sample(mut self, sample: TableSample) -> Self {
+        self.sample = sample;
+        self
+    }
+
     pub fn schema_name(&self) -> SchemaName {
         self.schema_name.clone()
     }
@@ -94,6 +406,21 @@ impl QueryHashDataInput {
         self.table_name.clone()
     }
 
+    /// Returns the name of the table in the second database, which defaults to its name in the first.
+    pub fn second_table_name(&self) -> TableName {
+        self.second_table_name
+            .clone()
+            .unwrap_or_else(|| self.table_name.clone())
+    }
+
+    pub fn projection(&self) -> TableProjection {
+        self.projection.clone()
+    }
+
+    pub fn hash_expression(&self) -> TableHashExpression {
+        self.hash_expression.clone()
+    }
+
     pub fn primary_keys(&self) -> TablePrimaryKeys {
         self.primary_keys.clone()
     }
@@ -105,19 +432,158 @@ impl QueryHashDataInput {
     pub fn offset(&self) -> TableOffset {
         self.offset.clone()
     }
+
+    pub fn first_filter(&self) -> TableFilter {
+        self.first_filter.clone()
+    }
+
+    pub fn second_filter(&self) -> TableFilter {
+        self.second_filter.clone()
+    }
+
+    pub fn sample(&self) -> TableSample {
+        self.sample.clone()
+    }
 }
 
 /// Represents the input for querying primary keys.
 pub struct QueryPrimaryKeysInput {
+    schema_name: SchemaName,
     table_name: String,
 }
 
 impl QueryPrimaryKeysInput {
-    pub fn new(table_name: String) -> Self {
-        Self { table_name }
+    pub fn new(schema_name: SchemaName, table_name: String) -> Self {
+        Self {
+            schema_name,
+            table_name,
+        }
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
     }
 
     pub fn table_name(&self) -> String {
         self.table_name.to_string()
     }
 }
+
+/// Represents the input for counting the rows of several tables at once.
+pub struct QueryTableCountsInput {
+    schema_name: SchemaName,
+    table_names: Vec<(TableName, TableName)>,
+    first_filter: TableFilter,
+    second_filter: TableFilter,
+}
+
+impl QueryTableCountsInput {
+    /// Creates a new `QueryTableCountsInput` instance.
+    ///
+    /// Every table is paired with its name in the second database.
+    /// The filters are applied to the first and the second database respectively.
+    pub fn new(
+        schema_name: SchemaName,
+        table_names: Vec<(TableName, TableName)>,
+        first_filter: TableFilter,
+        second_filter: TableFilter,
+    ) -> Self {
+        Self {
+            schema_name,
+            table_names,
+            first_filter,
+            second_filter,
+        }
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
+    }
+
+    pub fn table_names(&self) -> &[(TableName, TableName)] {
+        &self.table_names
+    }
+
+    pub fn first_filter(&self) -> &TableFilter {
+        &self.first_filter
+    }
+
+    pub fn second_filter(&self) -> &TableFilter {
+        &self.second_filter
+    }
+}
+
+/// Represents the input for querying the primary keys of several tables at once.
+pub struct QueryAllPrimaryKeysInput {
+    schema_name: SchemaName,
+    table_names: Vec<String>,
+}
+
+impl QueryAllPrimaryKeysInput {
+    pub fn new(schema_name: SchemaName, table_names: Vec<String>) -> Self {
+        Self {
+            schema_name,
+            table_names,
+        }
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
+    }
+
+    pub fn table_names(&self) -> &[String] {
+        &self.table_names
+    }
+}
+
+/// Represents the input for querying the estimated row counts of all the tables of a schema.
+pub struct QueryRowEstimatesInput {
+    schema_name: SchemaName,
+}
+
+impl QueryRowEstimatesInput {
+    pub fn new(schema_name: SchemaName) -> Self {
+        Self { schema_name }
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
+    }
+}
+
+/// Represents the input for querying the columns of a table.
+pub struct QueryTableColumnsInput {
+    schema_name: SchemaName,
+    table_name: TableName,
+    second_table_name: Option<TableName>,
+}
+
+impl QueryTableColumnsInput {
+    /// Creates a new `QueryTableColumnsInput` instance.
+    pub fn new(schema_name: SchemaName, table_name: TableName) -> Self {
+        Self {
+            schema_name,
+            table_name,
+            second_table_name: None,
+        }
+    }
+
+    /// Sets the name of the table in the second database, when it differs from its name in the first.
+    pub fn with_second_table_name(mut self, second_table_name: TableName) -> Self {
+        self.second_table_name = Some(second_table_name);
+        self
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
+    }
+
+    pub fn table_name(&self) -> &TableName {
+        &self.table_name
+    }
+
+    /// Returns the name of the table in the second database, which defaults to its name in the first.
+    pub fn second_table_name(&self) -> &TableName {
+        self.second_table_name.as_ref().unwrap_or(&self.table_name)
+    }
+}
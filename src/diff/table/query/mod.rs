@@ -1,5 +1,7 @@
+pub mod copy_stream;
 pub mod input;
 pub mod output;
 pub mod table_query;
 pub mod table_query_executor;
+pub mod table_snapshot_query_executor;
 pub mod table_types;
@@ -2,22 +2,61 @@ use colored::{ColoredString, Colorize};
 use std::fmt::Display;
 
 use crate::diff::diff_output::DiffOutput;
-use crate::diff::types::DiffOutputMarker;
+use crate::diff::types::{DiffOutputMarker, SchemaName};
 use std::time::Duration;
 
-/// Represents the source of a table (either the first or the second).
+/// Represents the source of a table (either the first or the second),
+/// along with the alias given to its database, if any.
 #[cfg_attr(test, derive(PartialEq))]
 #[derive(Debug, Clone)]
 pub enum TableSource {
-    First,
-    Second,
+    First(Option<String>),
+    Second(Option<String>),
+}
+
+impl TableSource {
+    /// Labels the source with the alias given to its database, if any.
+    pub fn with_alias(self, first_alias: Option<&str>, second_alias: Option<&str>) -> Self {
+        match self {
+            Self::First(_) => Self::First(first_alias.map(str::to_owned)),
+            Self::Second(_) => Self::Second(second_alias.map(str::to_owned)),
+        }
+    }
 }
 
 impl Display for TableSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::First => write!(f, "first"),
-            Self::Second => write!(f, "second"),
+            Self::First(alias) => write!(f, "{}", alias.as_deref().unwrap_or("first")),
+            Self::Second(alias) => write!(f, "{}", alias.as_deref().unwrap_or("second")),
+        }
+    }
+}
+
+/// Represents how the rows of two tables with different counts drifted apart,
+/// as told by sampling hash windows over the rows they have in common.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone)]
+pub enum TableDrift {
+    /// Every sampled window matches, so rows were only appended to (or removed from) the end.
+    AppendOnly,
+    /// The sampled windows, as `[start, end)` row ranges, that differ between the tables.
+    Scattered(Vec<(i64, i64)>),
+}
+
+impl Display for TableDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AppendOnly => write!(f, "append-only drift"),
+            Self::Scattered(windows) => write!(
+                f,
+                "scattered divergence between rows {}",
+                windows
+                    .iter()
+                    .map(|(start, end)| format!("[{}, {})", start, end))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -25,12 +64,18 @@ impl Display for TableSource {
 /// Represents the difference in table counts between two tables.
 #[cfg_attr(test, derive(PartialEq))]
 #[derive(Debug, Clone)]
-pub struct TableCountDiff(i64, i64);
+pub struct TableCountDiff(i64, i64, Option<TableDrift>);
 
 impl TableCountDiff {
     /// Creates a new `TableCountDiff` instance with the given counts.
     pub fn new(first: i64, second: i64) -> Self {
-        Self(first, second)
+        Self(first, second, None)
+    }
+
+    /// Attaches how the rows of the tables drifted apart, if it was sampled.
+    pub fn with_drift(mut self, drift: Option<TableDrift>) -> Self {
+        self.2 = drift;
+        self
     }
 
     pub fn first(&self) -> i64 {
@@ -40,6 +85,66 @@ impl TableCountDiff {
     pub fn second(&self) -> i64 {
         self.1
     }
+
+    pub fn drift(&self) -> Option<&TableDrift> {
+        self.2.as_ref()
+    }
+
+    /// Returns the absolute difference between the counts of the tables.
+    pub fn difference(&self) -> i64 {
+        self.0.abs_diff(self.1) as i64
+    }
+
+    /// Returns the difference between the counts of the tables, relative to the larger of them
+    /// (e.g. 0.03 for tables of 1000 and 970 rows), or 0 when both tables are empty.
+    pub fn relative_difference(&self) -> f64 {
+        let largest = self.0.max(self.1);
+        if largest <= 0 {
+            return 0.0;
+        }
+
+        self.difference() as f64 / largest as f64
+    }
+}
+
+/// Represents how long the phases of a table comparison took: counting the rows of the tables,
+/// then hashing their data.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone, Copy)]
+pub struct TableTimings {
+    count: Duration,
+    hash: Duration,
+}
+
+impl TableTimings {
+    pub fn new(count: Duration, hash: Duration) -> Self {
+        Self { count, hash }
+    }
+
+    pub fn count(&self) -> Duration {
+        self.count
+    }
+
+    pub fn hash(&self) -> Duration {
+        self.hash
+    }
+
+    /// Returns the duration of both phases.
+    pub fn total(&self) -> Duration {
+        self.count + self.hash
+    }
+}
+
+impl Display for TableTimings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}ms (counts: {}ms, hashes: {}ms)",
+            self.total().as_millis(),
+            self.count.as_millis(),
+            self.hash.as_millis()
+        )
+    }
 }
 
 /// Represents the output of a table difference.
@@ -48,76 +153,215 @@ impl TableCountDiff {
 pub enum TableDiffOutput {
     /// Indicates that there is no difference between the tables.
     NoCountDiff(String, i64),
-    /// Indicates that there is no difference between the tables, along with the duration of the comparison.
-    NoDiffWithDuration(String, Duration),
+    /// Indicates that there is no difference between the tables, along with the durations of the comparison.
+    NoDiffWithDuration(String, TableTimings),
     /// Indicates that the table does not exist in a specific source.
     NotExists(String, TableSource),
     /// Indicates a difference in table counts.
     Diff(String, TableCountDiff),
+    /// Indicates that counting the rows of the table exceeded the statement timeout in the given source.
+    Timeout(String, TableSource),
+    /// Indicates that counting the rows of the table failed, along with the error message.
+    Error(String, String),
+    /// Indicates that the table is empty in the given source, while the other source has the given rows.
+    EmptyOnOneSide(String, TableSource, i64),
     /// Indicates that no primary key was found in the table.
     NoPrimaryKeyFound(String),
-    /// Indicates a difference in table data, along with the duration of the comparison.
-    DataDiffWithDuration(String, i64, i64, Duration),
+    /// Indicates a difference in table data, along with the durations of the comparison.
+    DataDiffWithDuration(String, i64, i64, TableTimings),
+    /// Indicates the approximate row counts of the tables, read from the planner statistics.
+    /// Estimates that differ are counted as a difference, even though they may not be exact.
+    EstimatedCount(String, i64, i64),
+    /// Indicates the output of comparing a view or a materialized view, rather than a table.
+    View(Box<TableDiffOutput>),
+    /// Indicates the output of hashing only the given percentage of the pages of the table,
+    /// which is probabilistic rather than exhaustive.
+    Sampled(Box<TableDiffOutput>, u64),
 }
 
 impl TableDiffOutput {
+    /// Returns the name of the table this output refers to.
+    pub fn table_name(&self) -> &str {
+        match self {
+            Self::NoCountDiff(table, _)
+            | Self::NoDiffWithDuration(table, _)
+            | Self::NotExists(table, _)
+            | Self::Diff(table, _)
+            | Self::EmptyOnOneSide(table, _, _)
+            | Self::Timeout(table, _)
+            | Self::Error(table, _)
+            | Self::NoPrimaryKeyFound(table)
+            | Self::DataDiffWithDuration(table, _, _, _)
+            | Self::EstimatedCount(table, _, _) => table,
+            Self::View(output) | Self::Sampled(output, _) => output.table_name(),
+        }
+    }
+
+    /// Returns the output of comparing the relation, whether it is a table or a view,
+    /// and whether it was sampled or not.
+    pub fn relation_output(&self) -> &TableDiffOutput {
+        match self {
+            Self::View(output) | Self::Sampled(output, _) => output.relation_output(),
+            output => output,
+        }
+    }
+
+    /// Determines whether the output refers to a view or a materialized view.
+    pub fn is_view(&self) -> bool {
+        matches!(self, Self::View(_))
+    }
+
+    /// Returns the kind of the relation this output refers to.
+    pub fn object(&self) -> &'static str {
+        if self.is_view() {
+            "view"
+        } else {
+            "table"
+        }
+    }
+
+    /// Determines whether the table differs between the databases, including when it is missing
+    /// or empty in one of them. Tables that could not be compared are not counted as differences.
+    pub fn is_difference(&self) -> bool {
+        match self {
+            Self::EstimatedCount(_, first, second) => first != second,
+            Self::View(output) | Self::Sampled(output, _) => output.is_difference(),
+            _ => matches!(
+                self,
+                Self::Diff(_, _)
+                    | Self::EmptyOnOneSide(_, _, _)
+                    | Self::NotExists(_, _)
+                    | Self::DataDiffWithDuration(_, _, _, _)
+            ),
+        }
+    }
+
     /// Determines whether the table difference should be skipped.
     pub fn skip_table_diff(&self) -> bool {
-        matches!(self, Self::Diff(_, _) | Self::NotExists(_, _))
+        matches!(
+            self.relation_output(),
+            Self::Diff(_, _)
+                | Self::EmptyOnOneSide(_, _, _)
+                | Self::NotExists(_, _)
+                | Self::Timeout(_, _)
+                | Self::Error(_, _)
+                | Self::EstimatedCount(_, _, _)
+        )
     }
 
-    /// Converts the table difference output to a colored string.
-    pub fn to_string(&self) -> ColoredString {
+    /// Labels the source of the output, if any, with the alias given to its database.
+    pub fn with_source_aliases(
+        self,
+        first_alias: Option<&str>,
+        second_alias: Option<&str>,
+    ) -> Self {
         match self {
-            Self::NoCountDiff(table, count) => {
-                format!("{} - No difference. Total rows: {}", table, count)
-                    .green()
-                    .bold()
+            Self::NotExists(table, source) => {
+                Self::NotExists(table, source.with_alias(first_alias, second_alias))
             }
-            Self::NotExists(table, source) => format!("{} - Does not exist in {}", table, source)
-                .red()
-                .bold()
-                .underline(),
-            Self::Diff(table, diffs) => format!(
-                "{} - First table rows: {}, Second table rows: {}",
-                table,
-                diffs.first(),
-                diffs.second()
-            )
-            .red()
-            .bold(),
-            TableDiffOutput::NoPrimaryKeyFound(table) => {
-                format!("{} - No primary key found", table).red().bold()
+            Self::Timeout(table, source) => {
+                Self::Timeout(table, source.with_alias(first_alias, second_alias))
             }
-            TableDiffOutput::NoDiffWithDuration(table, duration) => {
-                format!("{} - No difference in {}ms", table, duration.as_millis())
-                    .green()
-                    .bold()
+            Self::EmptyOnOneSide(table, source, rows) => {
+                Self::EmptyOnOneSide(table, source.with_alias(first_alias, second_alias), rows)
+            }
+            Self::View(output) => Self::View(Box::new(
+                output.with_source_aliases(first_alias, second_alias),
+            )),
+            Self::Sampled(output, percent) => Self::Sampled(
+                Box::new(output.with_source_aliases(first_alias, second_alias)),
+                percent,
+            ),
+            output => output,
+        }
+    }
+
+    /// Returns the durations of the comparison, if the data of the tables was compared.
+    pub fn timings(&self) -> Option<&TableTimings> {
+        match self.relation_output() {
+            Self::NoDiffWithDuration(_, timings) | Self::DataDiffWithDuration(_, _, _, timings) => {
+                Some(timings)
             }
-            TableDiffOutput::DataDiffWithDuration(table_name, position, offset, duration) => {
+            _ => None,
+        }
+    }
+
+    /// Returns the plain, uncolored message of the output (e.g. for machine-readable reports).
+    pub fn message(&self) -> String {
+        match self {
+            Self::NoCountDiff(table, count) => {
+                format!("{} - No difference. Total rows: {}", table, count)
+            }
+            Self::NotExists(table, source) => format!("{} - Does not exist in {}", table, source),
+            Self::Diff(table, diffs) => {
+                let drift = diffs
+                    .drift()
+                    .map(|drift| format!(" ({})", drift))
+                    .unwrap_or_default();
                 format!(
-                    "{} - Data diff between rows [{},{}] - in {}ms",
-                    table_name,
-                    position,
-                    offset,
-                    duration.as_millis()
+                    "{} - First table rows: {}, Second table rows: {}, off by {} ({:.2}%){}",
+                    table,
+                    diffs.first(),
+                    diffs.second(),
+                    diffs.difference(),
+                    diffs.relative_difference() * 100.0,
+                    drift
                 )
-                .red()
-                .bold()
             }
+            Self::EmptyOnOneSide(table, source, rows) => format!(
+                "{} - Empty in {}, while the other table has {} rows",
+                table, source, rows
+            ),
+            Self::Timeout(table, source) => format!("{} - Query timed out in {}", table, source),
+            Self::Error(table, message) => format!("{} - Query failed: {}", table, message),
+            Self::NoPrimaryKeyFound(table) => format!("{} - No primary key found", table),
+            Self::NoDiffWithDuration(table, timings) => {
+                format!("{} - No difference in {}", table, timings)
+            }
+            Self::DataDiffWithDuration(table_name, position, offset, timings) => format!(
+                "{} - Data diff between rows [{},{}] - in {}",
+                table_name, position, offset, timings
+            ),
+            Self::EstimatedCount(table, first, second) if first == second => {
+                format!("{} - No difference. Estimated rows: {}", table, first)
+            }
+            Self::EstimatedCount(table, first, second) => format!(
+                "{} - First table estimated rows: {}, Second table estimated rows: {}",
+                table, first, second
+            ),
+            Self::View(output) => format!("[view] {}", output.message()),
+            Self::Sampled(output, percent) => format!(
+                "[sampled {}%, not exhaustive] {}",
+                percent,
+                output.message()
+            ),
         }
     }
-}
 
-impl DiffOutputMarker for TableDiffOutput {
-    fn convert(self) -> DiffOutput {
-        DiffOutput::TableDiff(self.clone())
+    /// Converts the table difference output to a colored string, colored by its outcome.
+    pub fn to_string(&self) -> ColoredString {
+        let message = self.message();
+        let matched = !self.is_difference()
+            && matches!(
+                self.relation_output(),
+                Self::NoCountDiff(_, _)
+                    | Self::NoDiffWithDuration(_, _)
+                    | Self::EstimatedCount(_, _, _)
+            );
+
+        match self {
+            _ if matched => message.green().bold(),
+            Self::NotExists(_, _) | Self::Timeout(_, _) | Self::Error(_, _) => {
+                message.red().bold().underline()
+            }
+            _ => message.red().bold(),
+        }
     }
 }
 
-impl From<TableDiffOutput> for DiffOutput {
-    fn from(val: TableDiffOutput) -> Self {
-        DiffOutput::TableDiff(val)
+impl DiffOutputMarker for TableDiffOutput {
+    fn convert(self, schema_name: SchemaName) -> DiffOutput {
+        DiffOutput::TableDiff(schema_name, self)
     }
 }
 
@@ -128,23 +372,174 @@ mod tests {
     #[test]
     fn test_skip_table_when_needed() {
         let no_count_diff = TableDiffOutput::NoCountDiff("test".to_string(), 1000);
-        let not_exists = TableDiffOutput::NotExists("test".to_string(), TableSource::First);
+        let not_exists = TableDiffOutput::NotExists("test".to_string(), TableSource::First(None));
         let diff = TableDiffOutput::Diff("test".to_string(), TableCountDiff::new(1, 2));
+        let empty_on_one_side =
+            TableDiffOutput::EmptyOnOneSide("test".to_string(), TableSource::First(None), 2);
+        let timeout = TableDiffOutput::Timeout("test".to_string(), TableSource::Second(None));
+        let error = TableDiffOutput::Error("test".to_string(), "permission denied".to_string());
         let no_primary_key = TableDiffOutput::NoPrimaryKeyFound("test".to_string());
-        let no_diff_with_duration =
-            TableDiffOutput::NoDiffWithDuration("test".to_string(), Duration::from_millis(1));
+        let no_diff_with_duration = TableDiffOutput::NoDiffWithDuration(
+            "test".to_string(),
+            TableTimings::new(Duration::ZERO, Duration::from_millis(1)),
+        );
         let data_diff_with_duration = TableDiffOutput::DataDiffWithDuration(
             "test".to_string(),
             1,
             2,
-            Duration::from_millis(1),
+            TableTimings::new(Duration::ZERO, Duration::from_millis(1)),
         );
 
         assert!(not_exists.skip_table_diff());
         assert!(diff.skip_table_diff());
+        assert!(empty_on_one_side.skip_table_diff());
+        assert!(timeout.skip_table_diff());
+        assert!(error.skip_table_diff());
         assert!(!no_count_diff.skip_table_diff());
         assert!(!no_primary_key.skip_table_diff());
         assert!(!no_diff_with_duration.skip_table_diff());
         assert!(!data_diff_with_duration.skip_table_diff());
     }
+
+    #[test]
+    fn test_count_diff_with_drift() {
+        let scattered = TableDiffOutput::Diff(
+            "test".to_string(),
+            TableCountDiff::new(1000, 1200)
+                .with_drift(Some(TableDrift::Scattered(vec![(0, 100), (400, 500)]))),
+        );
+        let append_only = TableDiffOutput::Diff(
+            "test".to_string(),
+            TableCountDiff::new(1000, 1200).with_drift(Some(TableDrift::AppendOnly)),
+        );
+
+        assert_eq!(
+            &*scattered.to_string(),
+            "test - First table rows: 1000, Second table rows: 1200, off by 200 (16.67%) \
+             (scattered divergence between rows [0, 100), [400, 500))"
+        );
+        assert_eq!(
+            &*append_only.to_string(),
+            "test - First table rows: 1000, Second table rows: 1200, off by 200 (16.67%) (append-only drift)"
+        );
+    }
+
+    #[test]
+    fn test_estimated_count() {
+        let same = TableDiffOutput::EstimatedCount("test".to_string(), 1000, 1000);
+        let different = TableDiffOutput::EstimatedCount("test".to_string(), 1000, 1200);
+
+        assert!(same.skip_table_diff());
+        assert!(!same.is_difference());
+        assert!(different.is_difference());
+        assert_eq!(
+            &*same.to_string(),
+            "test - No difference. Estimated rows: 1000"
+        );
+        assert_eq!(
+            &*different.to_string(),
+            "test - First table estimated rows: 1000, Second table estimated rows: 1200"
+        );
+    }
+
+    #[test]
+    fn test_not_exists_with_source_aliases() {
+        let not_exists = TableDiffOutput::NotExists("test".to_string(), TableSource::Second(None));
+
+        assert_eq!(&*not_exists.to_string(), "test - Does not exist in second");
+        assert_eq!(
+            &*not_exists
+                .with_source_aliases(Some("prod"), Some("staging"))
+                .to_string(),
+            "test - Does not exist in staging"
+        );
+    }
+
+    #[test]
+    fn test_view_delegates_to_its_output() {
+        let view = TableDiffOutput::View(Box::new(TableDiffOutput::NotExists(
+            "view1".to_string(),
+            TableSource::Second(None),
+        )));
+
+        assert!(view.is_view());
+        assert_eq!("view", view.object());
+        assert_eq!("view1", view.table_name());
+        assert!(view.is_difference());
+        assert!(view.skip_table_diff());
+        assert_eq!(
+            "[view] view1 - Does not exist in replica",
+            &*view.with_source_aliases(None, Some("replica")).to_string()
+        );
+    }
+
+    #[test]
+    fn test_sampled_delegates_to_its_output() {
+        let sampled = TableDiffOutput::Sampled(
+            Box::new(TableDiffOutput::DataDiffWithDuration(
+                "table1".to_string(),
+                0,
+                100,
+                TableTimings::new(Duration::ZERO, Duration::from_millis(1)),
+            )),
+            5,
+        );
+
+        assert!(!sampled.is_view());
+        assert_eq!("table1", sampled.table_name());
+        assert!(sampled.is_difference());
+        assert!(sampled.timings().is_some());
+        assert_eq!(
+            "[sampled 5%, not exhaustive] table1 - Data diff between rows [0,100] - in 1ms (counts: 0ms, hashes: 1ms)",
+            &*sampled.to_string()
+        );
+    }
+
+    #[test]
+    fn test_output_without_colors_has_no_escape_codes() {
+        let data_diff = TableDiffOutput::DataDiffWithDuration(
+            "table1".to_string(),
+            0,
+            100,
+            TableTimings::new(Duration::ZERO, Duration::from_millis(1)),
+        );
+
+        // As set by the client with --no-color or NO_COLOR
+        colored::control::set_override(false);
+        let rendered = format!("{}", data_diff.to_string());
+        colored::control::unset_override();
+
+        assert!(!rendered.contains('\x1b'));
+        assert_eq!(
+            "table1 - Data diff between rows [0,100] - in 1ms (counts: 0ms, hashes: 1ms)",
+            rendered
+        );
+    }
+
+    #[test]
+    fn test_message_is_plain_text() {
+        let not_exists = TableDiffOutput::NotExists("table1".to_string(), TableSource::First(None));
+        let sampled = TableDiffOutput::Sampled(
+            Box::new(TableDiffOutput::NoCountDiff("table1".to_string(), 10)),
+            5,
+        );
+
+        assert_eq!("table1 - Does not exist in first", not_exists.message());
+        assert_eq!(
+            "[sampled 5%, not exhaustive] table1 - No difference. Total rows: 10",
+            sampled.message()
+        );
+        assert_eq!(sampled.message(), &*sampled.to_string());
+    }
+
+    #[test]
+    fn test_count_diff_difference() {
+        let count_diff = TableCountDiff::new(1000, 970);
+
+        assert_eq!(count_diff.difference(), 30);
+        assert!((count_diff.relative_difference() - 0.03).abs() < f64::EPSILON);
+        assert_eq!(TableCountDiff::new(970, 1000).difference(), 30);
+        assert_eq!(TableCountDiff::new(0, 5).relative_difference(), 1.0);
+        assert_eq!(TableCountDiff::new(0, 0).relative_difference(), 0.0);
+    }
 }
@@ -1,34 +1,60 @@
 use crate::diff::table::query::table_types::{
-    IncludedExcludedTables, TableMode, TableName, TableOffset, TablePosition, TablePrimaryKeys,
+    IncludedExcludedTables, TableFilter, TableHashExpression, TableName, TableOffset,
+    TablePosition, TablePrimaryKeys, TableProjection, TableSample, TableTypes,
 };
 use crate::diff::types::SchemaName;
 use std::fmt::Display;
 
+/// The alias of the column holding the hash returned by [`TableQuery::HashQuery`], so that it is
+/// fetched by the same name whatever the hash expression (e.g. `sha256` instead of `md5`).
+pub const HASH_COLUMN: &str = "hash";
+
 pub enum TableQuery {
-    AllTablesForSchema(SchemaName, IncludedExcludedTables),
-    CountRowsForTable(SchemaName, TableName),
-    FindPrimaryKeyForTable(TableName),
+    AllTablesForSchema(SchemaName, IncludedExcludedTables, TableTypes),
+    AllViewsForSchema(SchemaName, IncludedExcludedTables),
+    CountRowsForTable(SchemaName, TableName, TableFilter, TableSample),
+    CountRowsForTables(SchemaName, Vec<TableName>, TableFilter),
+    EstimateRowsForTable(SchemaName, TableName),
+    EstimateRowsForTables(SchemaName),
+    FindPrimaryKeyForTable(SchemaName, TableName),
+    FindPrimaryKeysForTables(SchemaName, Vec<TableName>),
+    FindUniqueKeyForTable(SchemaName, TableName),
+    ColumnsForTable(SchemaName, TableName),
+    KeyRangeForTable(SchemaName, TableName, TablePrimaryKeys, TableFilter),
+    KeyCursorForTable(
+        SchemaName,
+        TableName,
+        TablePrimaryKeys,
+        TableOffset,
+        TableFilter,
+        TableSample,
+    ),
+    CopyRows(
+        SchemaName,
+        TableName,
+        TableProjection,
+        TablePrimaryKeys,
+        TableFilter,
+    ),
     HashQuery(
         SchemaName,
         TableName,
+        TableProjection,
+        TableHashExpression,
         TablePrimaryKeys,
         TablePosition,
         TableOffset,
+        TableFilter,
+        TableSample,
     ),
 }
 
 impl Display for TableQuery {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::AllTablesForSchema(schema_name, included_excluded_tables) => {
-                let inclusion_exclusion_statement = match included_excluded_tables.table_mode() {
-                    None => "".to_string(),
-                    Some(table_mode) => match table_mode {
-                        TableMode::Include => included_excluded_tables.inclusion_statement(),
-                        TableMode::Exclude => included_excluded_tables.exclusion_statement(),
-                    },
-                };
-
+            // Views are listed separately, so that they are only compared when asked to,
+            // and foreign tables are left out unless their type is opted in
+            Self::AllTablesForSchema(schema_name, included_excluded_tables, table_types) => {
                 write!(
                     f,
                     r#"
@@ -36,21 +62,82 @@ impl Display for TableQuery {
                 FROM information_schema.tables
                 WHERE table_schema = '{}'
                 {}
+                {}
                 "#,
                     schema_name.name(),
-                    inclusion_exclusion_statement
+                    table_types.statement(),
+                    included_excluded_tables.statement()
                 )
             }
+            Self::AllViewsForSchema(schema_name, included_excluded_tables) => write!(
+                f,
+                // language=postgresql
+                r#"
+                SELECT table_name
+                FROM (
+                    SELECT table_name::text FROM information_schema.views WHERE table_schema = '{}'
+                    UNION ALL
+                    SELECT matviewname::text FROM pg_matviews WHERE schemaname = '{}'
+                ) AS views
+                WHERE true
+                {}
+                "#,
+                schema_name.name(),
+                schema_name.name(),
+                included_excluded_tables.statement()
+            ),
             // https://stackoverflow.com/questions/7943233/fast-way-to-discover-the-row-count-of-a-table-in-postgresql
-            TableQuery::CountRowsForTable(schema_name, table_name) => {
+            TableQuery::CountRowsForTable(schema_name, table_name, table_filter, table_sample) => {
                 write!(
                     f,
-                    "SELECT count(*) FROM {}.{}",
+                    "SELECT count(*) FROM {}.{}{}{}",
                     schema_name.name(),
-                    table_name.name()
+                    table_name.name(),
+                    table_sample.clause(),
+                    table_filter.where_clause()
                 )
             }
-            TableQuery::FindPrimaryKeyForTable(table_name) => write!(
+            // Counts every table in a single round trip, labelling each count with the name of its table
+            TableQuery::CountRowsForTables(schema_name, table_names, table_filter) => write!(
+                f,
+                "{}",
+                table_names
+                    .iter()
+                    .map(|table_name| format!(
+                        "SELECT '{}' AS table_name, count(*) AS count FROM {}.{}{}",
+                        table_name.name().replace('\'', "''"),
+                        schema_name.name(),
+                        table_name.name(),
+                        table_filter.where_clause()
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(" UNION ALL ")
+            ),
+            // Prefers the live tuples tracked by the statistics collector over the planner estimate,
+            // which is negative for tables never vacuumed or analyzed. A missing table fails the cast.
+            TableQuery::EstimateRowsForTable(schema_name, table_name) => write!(
+                f,
+                // language=postgresql
+                r#"
+                SELECT COALESCE(s.n_live_tup, GREATEST(c.reltuples, 0)::bigint) AS count
+                FROM pg_class c
+                LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+                WHERE c.oid = {}"#,
+                qualified_regclass(schema_name, table_name.name())
+            ),
+            TableQuery::EstimateRowsForTables(schema_name) => write!(
+                f,
+                // language=postgresql
+                r#"
+                SELECT c.relname AS table_name, COALESCE(s.n_live_tup, GREATEST(c.reltuples, 0)::bigint) AS count
+                FROM pg_class c
+                JOIN pg_namespace n ON n.oid = c.relnamespace
+                LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+                WHERE n.nspname = '{}'
+                AND c.relkind IN ('r', 'p', 'f', 'v', 'm')"#,
+                schema_name.name().replace('\'', "''")
+            ),
+            TableQuery::FindPrimaryKeyForTable(schema_name, table_name) => write!(
                 f,
                 // language=postgresql
                 r#"
@@ -58,29 +145,166 @@ impl Display for TableQuery {
                 FROM   pg_index i
                 JOIN   pg_attribute a ON a.attrelid = i.indrelid
                                      AND a.attnum = ANY(i.indkey)
-                WHERE  i.indrelid = '{}'::regclass
-                AND    i.indisprimary"#,
+                WHERE  i.indrelid = {}
+                AND    i.indisprimary
+                AND    a.attnum > 0
+                AND    NOT a.attisdropped
+                ORDER  BY array_position(i.indkey::int2[], a.attnum)"#,
+                qualified_regclass(schema_name, table_name.name())
+            ),
+            // Scans the primary keys of all the tables at once, skipping the tables that no longer exist
+            TableQuery::FindPrimaryKeysForTables(schema_name, table_names) => write!(
+                f,
+                // language=postgresql
+                r#"
+                SELECT t.table_name, a.attname
+                FROM   unnest(ARRAY[{}]::text[]) AS t(table_name)
+                JOIN   pg_index i ON i.indrelid = to_regclass(format('%I.%I', '{}', t.table_name))
+                JOIN   pg_attribute a ON a.attrelid = i.indrelid
+                                     AND a.attnum = ANY(i.indkey)
+                WHERE  i.indisprimary
+                AND    a.attnum > 0
+                AND    NOT a.attisdropped
+                ORDER  BY t.table_name, array_position(i.indkey::int2[], a.attnum)"#,
+                table_names
+                    .iter()
+                    .map(|table_name| format!("'{}'", table_name.name().replace('\'', "''")))
+                    .collect::<Vec<String>>()
+                    .join(","),
+                schema_name.name().replace('\'', "''")
+            ),
+            // Picks the narrowest unique index that is neither partial nor expression based, and whose
+            // columns are all NOT NULL, since a unique index lets several rows hold NULLs
+            TableQuery::FindUniqueKeyForTable(schema_name, table_name) => write!(
+                f,
+                // language=postgresql
+                r#"
+                SELECT a.attname
+                FROM   pg_index i
+                JOIN   pg_attribute a ON a.attrelid = i.indrelid
+                                     AND a.attnum = ANY(i.indkey)
+                WHERE  i.indexrelid = (
+                    SELECT u.indexrelid
+                    FROM   pg_index u
+                    WHERE  u.indrelid = {}
+                    AND    u.indisunique
+                    AND    u.indpred IS NULL
+                    AND    u.indexprs IS NULL
+                    AND    NOT EXISTS (
+                        SELECT 1
+                        FROM   pg_attribute n
+                        WHERE  n.attrelid = u.indrelid
+                        AND    n.attnum = ANY(u.indkey)
+                        AND    NOT n.attnotnull
+                    )
+                    ORDER  BY u.indnatts, u.indexrelid
+                    LIMIT  1
+                )
+                AND    a.attnum > 0
+                AND    NOT a.attisdropped
+                ORDER  BY array_position(i.indkey::int2[], a.attnum)"#,
+                qualified_regclass(schema_name, table_name.name())
+            ),
+            // Resolves the default collation of a column to the one of the database,
+            // so that columns relying on different database defaults tell apart
+            TableQuery::ColumnsForTable(schema_name, table_name) => write!(
+                f,
+                // language=postgresql
+                r#"
+                SELECT column_name, data_type, is_nullable, column_default,
+                       col_description(format('%I.%I', table_schema, table_name)::regclass, ordinal_position::int) AS column_comment,
+                       (
+                           SELECT CASE c.collname
+                                      WHEN 'default' THEN (SELECT datcollate::text FROM pg_database WHERE datname = current_database())
+                                      ELSE c.collname::text
+                                  END
+                           FROM pg_attribute a
+                           JOIN pg_collation c ON c.oid = a.attcollation
+                           WHERE a.attrelid = format('%I.%I', table_schema, table_name)::regclass
+                           AND a.attname = column_name
+                       ) AS collation_name
+                FROM information_schema.columns
+                WHERE table_schema = '{}'
+                AND table_name = '{}'
+                ORDER BY ordinal_position"#,
+                schema_name.name(),
                 table_name.name()
             ),
+            TableQuery::KeyRangeForTable(schema_name, table_name, table_key, table_filter) => {
+                write!(
+                    f,
+                    "SELECT min({})::text AS min_key, max({})::text AS max_key FROM {}.{}{}",
+                    table_key.keys(),
+                    table_key.keys(),
+                    schema_name.name(),
+                    table_name.name(),
+                    table_filter.where_clause()
+                )
+            }
+            // Reads the keys of the row at the given offset, walking the index of the keys
+            TableQuery::KeyCursorForTable(
+                schema_name,
+                table_name,
+                table_primary_keys,
+                table_offset,
+                table_filter,
+                table_sample,
+            ) => {
+                write!(
+                    f,
+                    "SELECT {} AS key_cursor FROM {}.{}{}{} ORDER BY {} LIMIT 1 OFFSET {}",
+                    table_primary_keys.cursor_expression(),
+                    schema_name.name(),
+                    table_name.name(),
+                    table_sample.clause(),
+                    table_filter.where_clause(),
+                    table_primary_keys.keys(),
+                    table_offset.offset()
+                )
+            }
+            TableQuery::CopyRows(
+                schema_name,
+                table_name,
+                table_projection,
+                table_primary_keys,
+                table_filter,
+            ) => write!(
+                f,
+                "COPY (SELECT {} FROM {}.{}{} ORDER BY {}) TO STDOUT",
+                table_projection.select_list(),
+                schema_name.name(),
+                table_name.name(),
+                table_filter.where_clause(),
+                table_primary_keys.keys()
+            ),
             TableQuery::HashQuery(
                 schema_name,
                 table_name,
+                table_projection,
+                table_hash_expression,
                 table_primary_keys,
                 table_position,
                 table_offset,
+                table_filter,
+                table_sample,
             ) => {
                 write!(
                     f,
                     r#"
-                    SELECT md5(array_agg(md5((t.*)::varchar))::varchar)
+                    SELECT md5(array_agg({})::varchar) AS {}
                     FROM (
-                        SELECT *
-                        FROM {}.{}
+                        SELECT {}
+                        FROM {}.{}{}{}
                         ORDER BY {} limit {} offset {}
                     ) AS t
                     "#,
+                    table_hash_expression.expression(),
+                    HASH_COLUMN,
+                    table_projection.select_list(),
                     schema_name.name(),
                     table_name.name(),
+                    table_sample.clause(),
+                    table_filter.where_clause(),
                     table_primary_keys.keys(),
                     table_offset.offset(),
                     table_position.position(),
@@ -90,9 +314,21 @@ impl Display for TableQuery {
     }
 }
 
+/// Resolves a table of the given schema to its OID, whatever the search path.
+///
+/// Both names are quoted as identifiers, so that mixed case names resolve to the table they name.
+fn qualified_regclass(schema_name: &SchemaName, table_name: &str) -> String {
+    format!(
+        "format('%I.%I', '{}', '{}')::regclass",
+        schema_name.name().replace('\'', "''"),
+        table_name.replace('\'', "''")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diff::table::query::table_types::TableKeyRange;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -102,11 +338,16 @@ mod tests {
         let excluded_tables: Vec<String> = vec![];
         let included_excluded_tables =
             IncludedExcludedTables::new(included_tables, excluded_tables);
-        let query = TableQuery::AllTablesForSchema(schema_name, included_excluded_tables);
+        let query = TableQuery::AllTablesForSchema(
+            schema_name,
+            included_excluded_tables,
+            TableTypes::default(),
+        );
         let expected = r#"
                 SELECT table_name
                 FROM information_schema.tables
                 WHERE table_schema = 'public'
+                AND table_type = 'BASE TABLE'
                 AND table_name IN ('table1','table2')
                 "#;
         assert_eq!(expected, query.to_string());
@@ -119,36 +360,336 @@ mod tests {
         let excluded_tables = vec!["table1", "table2"];
         let included_excluded_tables =
             IncludedExcludedTables::new(included_tables, excluded_tables);
-        let query = TableQuery::AllTablesForSchema(schema_name, included_excluded_tables);
+        let query = TableQuery::AllTablesForSchema(
+            schema_name,
+            included_excluded_tables,
+            TableTypes::default(),
+        );
         let expected = r#"
                 SELECT table_name
                 FROM information_schema.tables
                 WHERE table_schema = 'public'
+                AND table_type = 'BASE TABLE'
                 AND table_name NOT IN ('table1','table2')
                 "#;
         assert_eq!(expected, query.to_string());
     }
 
+    #[test]
+    fn test_display_all_tables_for_schema_with_included_and_excluded_tables() {
+        let schema_name = SchemaName::new("public");
+        let included_tables = vec!["table1", "table2", "table3"];
+        let excluded_tables = vec!["table2"];
+        let included_excluded_tables =
+            IncludedExcludedTables::new(included_tables, excluded_tables);
+        let query = TableQuery::AllTablesForSchema(
+            schema_name,
+            included_excluded_tables,
+            TableTypes::default(),
+        );
+        let expected = r#"
+                SELECT table_name
+                FROM information_schema.tables
+                WHERE table_schema = 'public'
+                AND table_type = 'BASE TABLE'
+                AND table_name IN ('table1','table2','table3') AND table_name NOT IN ('table2')
+                "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_all_tables_for_schema_with_table_patterns() {
+        let schema_name = SchemaName::new("public");
+        let included_tables = vec!["table1", "audit_*"];
+        let excluded_tables = vec!["~_old$"];
+        let included_excluded_tables =
+            IncludedExcludedTables::new(included_tables, excluded_tables);
+        let query = TableQuery::AllTablesForSchema(
+            schema_name,
+            included_excluded_tables,
+            TableTypes::default(),
+        );
+        let expected = r#"
+                SELECT table_name
+                FROM information_schema.tables
+                WHERE table_schema = 'public'
+                AND table_type = 'BASE TABLE'
+                AND (table_name IN ('table1') OR table_name LIKE 'audit\_%') AND table_name !~ '_old$'
+                "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_all_tables_for_schema_with_table_types() {
+        let schema_name = SchemaName::new("public");
+        let included_excluded_tables =
+            IncludedExcludedTables::new(vec!["table1"], Vec::<String>::new());
+        let query = TableQuery::AllTablesForSchema(
+            schema_name,
+            included_excluded_tables,
+            TableTypes::new(vec!["FOREIGN"]),
+        );
+        let expected = r#"
+                SELECT table_name
+                FROM information_schema.tables
+                WHERE table_schema = 'public'
+                AND table_type IN ('BASE TABLE','FOREIGN')
+                AND table_name IN ('table1')
+                "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_all_views_for_schema_with_excluded_tables() {
+        let schema_name = SchemaName::new("public");
+        let included_excluded_tables =
+            IncludedExcludedTables::new(Vec::<String>::new(), vec!["view1"]);
+        let query = TableQuery::AllViewsForSchema(schema_name, included_excluded_tables);
+        let expected = r#"
+                SELECT table_name
+                FROM (
+                    SELECT table_name::text FROM information_schema.views WHERE table_schema = 'public'
+                    UNION ALL
+                    SELECT matviewname::text FROM pg_matviews WHERE schemaname = 'public'
+                ) AS views
+                WHERE true
+                AND table_name NOT IN ('view1')
+                "#;
+        assert_eq!(expected, query.to_string());
+    }
+
     #[test]
     fn test_display_count_rows_for_table() {
         let schema_name = SchemaName::new("public".to_string());
         let table_name = TableName::new("table1".to_string());
-        let query = TableQuery::CountRowsForTable(schema_name, table_name);
+        let query = TableQuery::CountRowsForTable(
+            schema_name,
+            table_name,
+            TableFilter::default(),
+            TableSample::default(),
+        );
         let expected = "SELECT count(*) FROM public.table1";
         assert_eq!(expected, query.to_string());
     }
 
+    #[test]
+    fn test_display_count_rows_for_table_with_filter() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_filter = TableFilter::new(vec!["valid_from <= '2024-01-01'"]);
+        let query = TableQuery::CountRowsForTable(
+            schema_name,
+            table_name,
+            table_filter,
+            TableSample::default(),
+        );
+        let expected = "SELECT count(*) FROM public.table1 WHERE (valid_from <= '2024-01-01')";
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_count_rows_for_tables() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_names = vec![TableName::new("table1"), TableName::new("table2")];
+        let table_filter = TableFilter::new(vec!["valid_from <= '2024-01-01'"]);
+        let query = TableQuery::CountRowsForTables(schema_name, table_names, table_filter);
+        let expected = "SELECT 'table1' AS table_name, count(*) AS count FROM public.table1 WHERE (valid_from <= '2024-01-01') \
+             UNION ALL \
+             SELECT 'table2' AS table_name, count(*) AS count FROM public.table2 WHERE (valid_from <= '2024-01-01')";
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_count_rows_for_table_with_combined_filter() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_filter = TableFilter::new(vec![
+            "created_at > now() - interval '7 days'",
+            "valid_from <= '2024-01-01'",
+        ]);
+        let query = TableQuery::CountRowsForTable(
+            schema_name,
+            table_name,
+            table_filter,
+            TableSample::default(),
+        );
+        let expected = "SELECT count(*) FROM public.table1 WHERE (created_at > now() - interval '7 days') AND (valid_from <= '2024-01-01')";
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_estimate_rows_for_table() {
+        let schema_name = SchemaName::new("Reporting".to_string());
+        let table_name = TableName::new("Table1".to_string());
+        let query = TableQuery::EstimateRowsForTable(schema_name, table_name);
+        let expected = r#"
+                SELECT COALESCE(s.n_live_tup, GREATEST(c.reltuples, 0)::bigint) AS count
+                FROM pg_class c
+                LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+                WHERE c.oid = format('%I.%I', 'Reporting', 'Table1')::regclass"#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_estimate_rows_for_tables() {
+        let schema_name = SchemaName::new("O'Reilly".to_string());
+        let query = TableQuery::EstimateRowsForTables(schema_name);
+        let expected = r#"
+                SELECT c.relname AS table_name, COALESCE(s.n_live_tup, GREATEST(c.reltuples, 0)::bigint) AS count
+                FROM pg_class c
+                JOIN pg_namespace n ON n.oid = c.relnamespace
+                LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+                WHERE n.nspname = 'O''Reilly'
+                AND c.relkind IN ('r', 'p', 'f', 'v', 'm')"#;
+        assert_eq!(expected, query.to_string());
+    }
+
     #[test]
     fn test_display_find_primary_key_for_table() {
+        let schema_name = SchemaName::new("reporting");
         let table_name = TableName::new("table1".to_string());
-        let query = TableQuery::FindPrimaryKeyForTable(table_name);
+        let query = TableQuery::FindPrimaryKeyForTable(schema_name, table_name);
         let expected = r#"
                 SELECT a.attname
                 FROM   pg_index i
                 JOIN   pg_attribute a ON a.attrelid = i.indrelid
                                      AND a.attnum = ANY(i.indkey)
-                WHERE  i.indrelid = 'table1'::regclass
-                AND    i.indisprimary"#;
+                WHERE  i.indrelid = format('%I.%I', 'reporting', 'table1')::regclass
+                AND    i.indisprimary
+                AND    a.attnum > 0
+                AND    NOT a.attisdropped
+                ORDER  BY array_position(i.indkey::int2[], a.attnum)"#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_find_primary_key_for_table_excludes_system_and_dropped_columns() {
+        let schema_name = SchemaName::new("public");
+        let table_name = TableName::new("table1".to_string());
+        let query = TableQuery::FindPrimaryKeyForTable(schema_name, table_name).to_string();
+        assert!(query.contains("a.attnum > 0"));
+        assert!(query.contains("NOT a.attisdropped"));
+    }
+
+    #[test]
+    fn test_display_find_primary_keys_for_tables() {
+        let schema_name = SchemaName::new("reporting");
+        let table_names = vec![TableName::new("table1"), TableName::new("table2")];
+        let query = TableQuery::FindPrimaryKeysForTables(schema_name, table_names);
+        let expected = r#"
+                SELECT t.table_name, a.attname
+                FROM   unnest(ARRAY['table1','table2']::text[]) AS t(table_name)
+                JOIN   pg_index i ON i.indrelid = to_regclass(format('%I.%I', 'reporting', t.table_name))
+                JOIN   pg_attribute a ON a.attrelid = i.indrelid
+                                     AND a.attnum = ANY(i.indkey)
+                WHERE  i.indisprimary
+                AND    a.attnum > 0
+                AND    NOT a.attisdropped
+                ORDER  BY t.table_name, array_position(i.indkey::int2[], a.attnum)"#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_find_unique_key_for_table() {
+        let schema_name = SchemaName::new("reporting");
+        let table_name = TableName::new("table1".to_string());
+        let query = TableQuery::FindUniqueKeyForTable(schema_name, table_name);
+        let expected = r#"
+                SELECT a.attname
+                FROM   pg_index i
+                JOIN   pg_attribute a ON a.attrelid = i.indrelid
+                                     AND a.attnum = ANY(i.indkey)
+                WHERE  i.indexrelid = (
+                    SELECT u.indexrelid
+                    FROM   pg_index u
+                    WHERE  u.indrelid = format('%I.%I', 'reporting', 'table1')::regclass
+                    AND    u.indisunique
+                    AND    u.indpred IS NULL
+                    AND    u.indexprs IS NULL
+                    AND    NOT EXISTS (
+                        SELECT 1
+                        FROM   pg_attribute n
+                        WHERE  n.attrelid = u.indrelid
+                        AND    n.attnum = ANY(u.indkey)
+                        AND    NOT n.attnotnull
+                    )
+                    ORDER  BY u.indnatts, u.indexrelid
+                    LIMIT  1
+                )
+                AND    a.attnum > 0
+                AND    NOT a.attisdropped
+                ORDER  BY array_position(i.indkey::int2[], a.attnum)"#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_columns_for_table() {
+        let schema_name = SchemaName::new("public");
+        let table_name = TableName::new("table1");
+        let query = TableQuery::ColumnsForTable(schema_name, table_name);
+        let expected = r#"
+                SELECT column_name, data_type, is_nullable, column_default,
+                       col_description(format('%I.%I', table_schema, table_name)::regclass, ordinal_position::int) AS column_comment,
+                       (
+                           SELECT CASE c.collname
+                                      WHEN 'default' THEN (SELECT datcollate::text FROM pg_database WHERE datname = current_database())
+                                      ELSE c.collname::text
+                                  END
+                           FROM pg_attribute a
+                           JOIN pg_collation c ON c.oid = a.attcollation
+                           WHERE a.attrelid = format('%I.%I', table_schema, table_name)::regclass
+                           AND a.attname = column_name
+                       ) AS collation_name
+                FROM information_schema.columns
+                WHERE table_schema = 'public'
+                AND table_name = 'table1'
+                ORDER BY ordinal_position"#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_key_range_for_table() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_key = TablePrimaryKeys::new("id".to_string());
+        let table_filter = TableFilter::new(vec!["a > 1"]);
+        let query = TableQuery::KeyRangeForTable(schema_name, table_name, table_key, table_filter);
+        let expected = "SELECT min(id)::text AS min_key, max(id)::text AS max_key FROM public.table1 WHERE (a > 1)";
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_copy_rows() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_projection = TableProjection::new(vec!["id", "name"]);
+        let table_primary_keys = TablePrimaryKeys::new("id".to_string());
+        let table_filter = TableFilter::new(vec!["a > 1"]);
+        let query = TableQuery::CopyRows(
+            schema_name,
+            table_name,
+            table_projection,
+            table_primary_keys,
+            table_filter,
+        );
+        let expected =
+            r#"COPY (SELECT "id", "name" FROM public.table1 WHERE (a > 1) ORDER BY id) TO STDOUT"#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_count_rows_for_table_with_key_range() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_filter =
+            TableFilter::default().and(TableKeyRange::new(0, 100).middle(50).condition("id"));
+        let query = TableQuery::CountRowsForTable(
+            schema_name,
+            table_name,
+            table_filter,
+            TableSample::default(),
+        );
+        let expected = "SELECT count(*) FROM public.table1 WHERE (id BETWEEN 25 AND 75)";
         assert_eq!(expected, query.to_string());
     }
 
@@ -162,12 +703,165 @@ mod tests {
         let query = TableQuery::HashQuery(
             schema_name,
             table_name,
+            TableProjection::default(),
+            TableHashExpression::default(),
+            table_primary_keys,
+            table_position,
+            table_offset,
+            TableFilter::default(),
+            TableSample::default(),
+        );
+        let expected = r#"
+                    SELECT md5(array_agg(md5((t.*)::varchar))::varchar) AS hash
+                    FROM (
+                        SELECT *
+                        FROM public.table1
+                        ORDER BY id limit 100 offset 0
+                    ) AS t
+                    "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_hash_query_with_binary_collation() {
+        let table_primary_keys =
+            TablePrimaryKeys::from_columns(vec!["tenant".to_string(), "id".to_string()])
+                .with_binary_collation(&["tenant".to_string()]);
+        let query = TableQuery::HashQuery(
+            SchemaName::new("public".to_string()),
+            TableName::new("table1".to_string()),
+            TableProjection::default(),
+            TableHashExpression::default(),
+            table_primary_keys,
+            TablePosition::new(0),
+            TableOffset::new(100),
+            TableFilter::default(),
+            TableSample::default(),
+        );
+        let expected = r#"
+                    SELECT md5(array_agg(md5((t.*)::varchar))::varchar) AS hash
+                    FROM (
+                        SELECT *
+                        FROM public.table1
+                        ORDER BY "tenant" COLLATE "C", "id" limit 100 offset 0
+                    ) AS t
+                    "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_hash_query_with_filter() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_primary_keys = TablePrimaryKeys::new("id".to_string());
+        let table_position = TablePosition::new(0);
+        let table_offset = TableOffset::new(100);
+        let table_filter = TableFilter::new(vec!["valid_from <= '2024-01-01'"]);
+        let query = TableQuery::HashQuery(
+            schema_name,
+            table_name,
+            TableProjection::default(),
+            TableHashExpression::default(),
+            table_primary_keys,
+            table_position,
+            table_offset,
+            table_filter,
+            TableSample::default(),
+        );
+        let expected = r#"
+                    SELECT md5(array_agg(md5((t.*)::varchar))::varchar) AS hash
+                    FROM (
+                        SELECT *
+                        FROM public.table1 WHERE (valid_from <= '2024-01-01')
+                        ORDER BY id limit 100 offset 0
+                    ) AS t
+                    "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_hash_query_with_projection() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_projection = TableProjection::new(vec!["name", "id"]);
+        let table_primary_keys = TablePrimaryKeys::new("id".to_string());
+        let table_position = TablePosition::new(0);
+        let table_offset = TableOffset::new(100);
+        let query = TableQuery::HashQuery(
+            schema_name,
+            table_name,
+            table_projection,
+            TableHashExpression::default(),
+            table_primary_keys,
+            table_position,
+            table_offset,
+            TableFilter::default(),
+            TableSample::default(),
+        );
+        let expected = r#"
+                    SELECT md5(array_agg(md5((t.*)::varchar))::varchar) AS hash
+                    FROM (
+                        SELECT "id", "name"
+                        FROM public.table1
+                        ORDER BY id limit 100 offset 0
+                    ) AS t
+                    "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_hash_query_with_coalesced_columns() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_projection =
+            TableProjection::new(vec!["name", "id"]).with_coalesced_columns(vec!["name"]);
+        let table_primary_keys = TablePrimaryKeys::new("id".to_string());
+        let table_position = TablePosition::new(0);
+        let table_offset = TableOffset::new(100);
+        let query = TableQuery::HashQuery(
+            schema_name,
+            table_name,
+            table_projection,
+            TableHashExpression::default(),
             table_primary_keys,
             table_position,
             table_offset,
+            TableFilter::default(),
+            TableSample::default(),
         );
         let expected = r#"
-                    SELECT md5(array_agg(md5((t.*)::varchar))::varchar)
+                    SELECT md5(array_agg(md5((t.*)::varchar))::varchar) AS hash
+                    FROM (
+                        SELECT "id", COALESCE("name", '') AS "name"
+                        FROM public.table1
+                        ORDER BY id limit 100 offset 0
+                    ) AS t
+                    "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_hash_query_with_hash_expression() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_hash_expression =
+            TableHashExpression::new("md5(concat_ws('|', t.id, t.amount::numeric(10,2)))");
+        let table_primary_keys = TablePrimaryKeys::new("id".to_string());
+        let table_position = TablePosition::new(0);
+        let table_offset = TableOffset::new(100);
+        let query = TableQuery::HashQuery(
+            schema_name,
+            table_name,
+            TableProjection::default(),
+            table_hash_expression,
+            table_primary_keys,
+            table_position,
+            table_offset,
+            TableFilter::default(),
+            TableSample::default(),
+        );
+        let expected = r#"
+                    SELECT md5(array_agg(md5(concat_ws('|', t.id, t.amount::numeric(10,2))))::varchar) AS hash
                     FROM (
                         SELECT *
                         FROM public.table1
@@ -176,4 +870,66 @@ mod tests {
                     "#;
         assert_eq!(expected, query.to_string());
     }
+
+    #[test]
+    fn test_display_count_rows_for_table_with_sample() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_filter = TableFilter::new(vec!["valid_from <= '2024-01-01'"]);
+        let query = TableQuery::CountRowsForTable(
+            schema_name,
+            table_name,
+            table_filter,
+            TableSample::new(5, 42),
+        );
+        let expected = "SELECT count(*) FROM public.table1 TABLESAMPLE SYSTEM (5) REPEATABLE (42) WHERE (valid_from <= '2024-01-01')";
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_hash_query_with_sample() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_primary_keys = TablePrimaryKeys::new("id".to_string());
+        let table_position = TablePosition::new(0);
+        let table_offset = TableOffset::new(100);
+        let query = TableQuery::HashQuery(
+            schema_name,
+            table_name,
+            TableProjection::default(),
+            TableHashExpression::default(),
+            table_primary_keys,
+            table_position,
+            table_offset,
+            TableFilter::default(),
+            TableSample::new(5, 42),
+        );
+        let expected = r#"
+                    SELECT md5(array_agg(md5((t.*)::varchar))::varchar) AS hash
+                    FROM (
+                        SELECT *
+                        FROM public.table1 TABLESAMPLE SYSTEM (5) REPEATABLE (42)
+                        ORDER BY id limit 100 offset 0
+                    ) AS t
+                    "#;
+        assert_eq!(expected, query.to_string());
+    }
+
+    #[test]
+    fn test_display_key_cursor_for_table() {
+        let schema_name = SchemaName::new("public".to_string());
+        let table_name = TableName::new("table1".to_string());
+        let table_primary_keys = TablePrimaryKeys::from_columns(vec!["id".to_string()]);
+        let table_filter = TableFilter::new(vec![r#"("id") > ('42')"#]);
+        let query = TableQuery::KeyCursorForTable(
+            schema_name,
+            table_name,
+            table_primary_keys,
+            TableOffset::new(9999),
+            table_filter,
+            TableSample::default(),
+        );
+        let expected = r#"SELECT concat_ws(', ', quote_nullable("id")) AS key_cursor FROM public.table1 WHERE (("id") > ('42')) ORDER BY "id" LIMIT 1 OFFSET 9999"#;
+        assert_eq!(expected, query.to_string());
+    }
 }
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone)]
 pub struct TableName(String);
 
@@ -11,17 +13,150 @@ impl TableName {
     }
 }
 
+/// Represents the ordering of the rows of a table, by its key columns or by an expression.
+///
+/// Ordering by key columns allows paging through the rows by their keys (keyset pagination),
+/// rather than skipping the rows of the previous chunks with an `OFFSET`.
 #[derive(Clone)]
-pub struct TablePrimaryKeys(String);
+pub struct TablePrimaryKeys(String, Vec<String>);
 
 impl TablePrimaryKeys {
+    /// Creates an ordering by the given expression, through which rows are paged by offset.
     pub fn new(keys: impl Into<String>) -> Self {
-        Self(keys.into())
+        Self(keys.into(), Vec::new())
+    }
+
+    /// Builds the ordering keys from the columns of a key, quoting each of them.
+    ///
+    /// Columns keep the order they are given in, which is the order of the index they come from,
+    /// so that ordering by them can use the index. Repeated columns are only kept once.
+    pub fn from_columns(columns: Vec<String>) -> Self {
+        let mut keys: Vec<String> = Vec::with_capacity(columns.len());
+        for column in columns {
+            if !keys.contains(&column) {
+                keys.push(column);
+            }
+        }
+
+        let columns = keys
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<String>>();
+
+        Self(columns.join(", "), columns)
+    }
+
+    /// Determines whether rows can be ordered by the given key columns: there is at least one,
+    /// and none is empty or the placeholder left behind by a dropped column
+    /// (e.g. `........pg.dropped.3........`).
+    pub fn is_well_formed(columns: &[String]) -> bool {
+        !columns.is_empty()
+            && columns.iter().all(|column| {
+                !column.trim().is_empty() && !column.starts_with("........pg.dropped.")
+            })
+    }
+
+    /// Returns a copy of the ordering comparing the given key columns byte by byte (`COLLATE "C"`),
+    /// whatever their collation, e.g. when it differs between both databases.
+    pub fn with_binary_collation(&self, columns: &[String]) -> Self {
+        if columns.is_empty() || !self.is_keyset() {
+            return self.clone();
+        }
+
+        let quoted_columns = columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<String>>();
+
+        let keys = self
+            .1
+            .iter()
+            .map(|key| {
+                if quoted_columns.contains(key) {
+                    format!("{} COLLATE \"C\"", key)
+                } else {
+                    key.clone()
+                }
+            })
+            .collect::<Vec<String>>();
+
+        Self(keys.join(", "), keys)
+    }
+
+    /// Returns a copy of the ordering through which rows are paged by offset, e.g. when the key
+    /// columns may hold NULLs, which never compare as greater than a cursor.
+    pub fn without_keyset(&self) -> Self {
+        Self::new(self.0.clone())
     }
 
     pub fn keys(&self) -> &str {
         &self.0
     }
+
+    /// Determines whether the rows can be paged by their keys, rather than by offset.
+    pub fn is_keyset(&self) -> bool {
+        !self.1.is_empty()
+    }
+
+    /// Returns the expression rendering the keys of a row as a list of SQL literals,
+    /// from which a [`TableKeyCursor`] is read.
+    pub fn cursor_expression(&self) -> String {
+        format!(
+            "concat_ws(', ', {})",
+            self.1
+                .iter()
+                .map(|column| format!("quote_nullable({})", column))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+
+    /// Returns the condition selecting the rows ordered after the given cursor.
+    pub fn after(&self, cursor: &TableKeyCursor) -> String {
+        format!("({}) > ({})", self.0, cursor.literals())
+    }
+
+    /// Returns the given key columns that are missing from the terms of an `ORDER BY` expression
+    /// (e.g. `created_at DESC, "Id"`). Rows are only ordered uniquely when none are missing.
+    pub fn missing_from<'a>(expression: &str, key_columns: &'a [String]) -> Vec<&'a str> {
+        let ordering_columns = expression
+            .split(',')
+            .map(|term| {
+                let term = term.trim();
+                match term.strip_prefix('"') {
+                    Some(quoted) => quoted.split('"').next().unwrap_or_default().to_string(),
+                    // Unquoted identifiers are folded to lower case
+                    None => term
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_lowercase(),
+                }
+            })
+            .collect::<Vec<String>>();
+
+        key_columns
+            .iter()
+            .filter(|column| !ordering_columns.contains(column))
+            .map(|column| column.as_str())
+            .collect()
+    }
+}
+
+/// Represents the keys of the last row of a chunk, as a list of SQL literals
+/// (e.g. `'42', 'abc'`), after which the next chunk starts.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone)]
+pub struct TableKeyCursor(String);
+
+impl TableKeyCursor {
+    pub fn new(literals: impl Into<String>) -> Self {
+        Self(literals.into())
+    }
+
+    pub fn literals(&self) -> &str {
+        &self.0
+    }
 }
 
 #[derive(Clone)]
@@ -50,6 +185,492 @@ impl TableOffset {
     }
 }
 
+/// Represents the definition of a table column.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableColumn {
+    name: String,
+    data_type: String,
+    is_nullable: bool,
+    default: Option<String>,
+    comment: Option<String>,
+    #[serde(default)]
+    collation: Option<String>,
+}
+
+impl TableColumn {
+    pub fn new(
+        name: impl Into<String>,
+        data_type: impl Into<String>,
+        is_nullable: bool,
+        default: Option<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            data_type: data_type.into(),
+            is_nullable,
+            default,
+            comment: None,
+            collation: None,
+        }
+    }
+
+    /// Attaches the comment of the column, as set through `COMMENT ON COLUMN`.
+    pub fn with_comment(mut self, comment: Option<String>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn data_type(&self) -> &str {
+        &self.data_type
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.is_nullable
+    }
+
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Attaches the collation of the column, `None` when its type is not collatable.
+    pub fn with_collation(mut self, collation: Option<String>) -> Self {
+        self.collation = collation;
+        self
+    }
+
+    pub fn collation(&self) -> Option<&str> {
+        self.collation.as_deref()
+    }
+
+    /// Determines whether the column holds text, as reported by `information_schema.columns`.
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self.data_type.as_str(),
+            "text" | "character varying" | "character"
+        )
+    }
+
+    /// Returns the key columns that may hold NULLs, according to the given columns of their table.
+    ///
+    /// Primary keys never do, but the columns of a unique key may, and rows sharing NULL keys
+    /// have no defined order.
+    pub fn nullable_keys<'a>(keys: &'a [String], columns: &[TableColumn]) -> Vec<&'a str> {
+        keys.iter()
+            .filter(|key| {
+                columns
+                    .iter()
+                    .any(|column| column.name() == key.as_str() && column.is_nullable())
+            })
+            .map(|key| key.as_str())
+            .collect()
+    }
+
+    /// Returns the key columns whose collation differs between the given columns of both tables.
+    ///
+    /// Such columns sort text differently in each database, so that ordering by them would
+    /// split identical rows into different chunks.
+    pub fn collation_mismatches<'a>(
+        keys: &'a [String],
+        first_columns: &[TableColumn],
+        second_columns: &[TableColumn],
+    ) -> Vec<&'a str> {
+        fn collation_of<'c>(columns: &'c [TableColumn], key: &str) -> Option<&'c str> {
+            columns
+                .iter()
+                .find(|column| column.name() == key)
+                .and_then(|column| column.collation())
+        }
+
+        keys.iter()
+            .filter(|key| {
+                collation_of(first_columns, key.as_str())
+                    != collation_of(second_columns, key.as_str())
+            })
+            .map(|key| key.as_str())
+            .collect()
+    }
+}
+
+/// Represents the conditions restricting which rows of a table take part in the comparison.
+#[derive(Clone, Default)]
+pub struct TableFilter(Vec<String>);
+
+impl TableFilter {
+    pub fn new(conditions: Vec<impl Into<String>>) -> Self {
+        Self(conditions.into_iter().map(|c| c.into()).collect())
+    }
+
+    pub fn conditions(&self) -> &Vec<String> {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a copy of the filter with the given condition added.
+    pub fn and(&self, condition: impl Into<String>) -> Self {
+        let mut conditions = self.0.clone();
+        conditions.push(condition.into());
+        Self(conditions)
+    }
+
+    /// Returns the `WHERE` clause (prefixed with a space) for the filter,
+    /// or an empty string when there are no conditions.
+    pub fn where_clause(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let conditions = self
+            .0
+            .iter()
+            .map(|condition| format!("({})", condition))
+            .collect::<Vec<String>>()
+            .join(" AND ");
+
+        format!(" WHERE {}", conditions)
+    }
+}
+
+/// Represents the sampling of the pages of a table through `TABLESAMPLE SYSTEM`.
+///
+/// Both databases are sampled with the same percentage and seed, which selects the same rows
+/// as long as the tables share their physical layout (e.g. on a physical replica). Tables copied
+/// logically may store their rows in other pages, in which case their samples differ.
+#[derive(Clone, Default)]
+pub struct TableSample(Option<(u64, u64)>);
+
+impl TableSample {
+    pub fn new(percent: u64, seed: u64) -> Self {
+        Self(Some((percent, seed)))
+    }
+
+    /// Returns the sampled percentage of the pages, if the table is sampled.
+    pub fn percent(&self) -> Option<u64> {
+        self.0.map(|(percent, _)| percent)
+    }
+
+    /// Returns the `TABLESAMPLE` clause (prefixed with a space) for the sample,
+    /// or an empty string when the whole table is read.
+    pub fn clause(&self) -> String {
+        match self.0 {
+            Some((percent, seed)) => {
+                format!(" TABLESAMPLE SYSTEM ({}) REPEATABLE ({})", percent, seed)
+            }
+            None => String::new(),
+        }
+    }
+}
+
+/// Represents the columns selected for hashing, in a canonical (alphabetical) order,
+/// so that the physical order of the columns does not affect the hashes.
+///
+/// Coalesced columns are selected as an empty string when NULL, so that both hash identically.
+#[derive(Clone, Default)]
+pub struct TableProjection(Vec<String>, Vec<String>);
+
+impl TableProjection {
+    pub fn new(columns: Vec<impl Into<String>>) -> Self {
+        let mut columns: Vec<String> = columns.into_iter().map(|c| c.into()).collect();
+        columns.sort();
+        columns.dedup();
+        Self(columns, Vec::new())
+    }
+
+    /// Marks the given columns to be selected as an empty string when NULL.
+    pub fn with_coalesced_columns(mut self, columns: Vec<impl Into<String>>) -> Self {
+        self.1 = columns.into_iter().map(|c| c.into()).collect();
+        self
+    }
+
+    pub fn columns(&self) -> &Vec<String> {
+        &self.0
+    }
+
+    pub fn coalesced_columns(&self) -> &Vec<String> {
+        &self.1
+    }
+
+    /// Returns the quoted column list, or `*` when no columns are known.
+    /// Coalesced columns keep their name, so that hash expressions may still reference them.
+    pub fn select_list(&self) -> String {
+        if self.0.is_empty() {
+            return "*".to_string();
+        }
+
+        self.0
+            .iter()
+            .map(|column| match self.1.contains(column) {
+                true => format!(
+                    "COALESCE({}, '') AS {}",
+                    quote_identifier(column),
+                    quote_identifier(column)
+                ),
+                false => quote_identifier(column),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+/// Quotes a column name, so that it keeps its case and may contain any character.
+fn quote_identifier(column: &str) -> String {
+    format!("\"{}\"", column.replace('"', "\"\""))
+}
+
+/// Represents the per-row hash expression of the hash query.
+///
+/// The `{row}` placeholder expands to the hashed row, while individual columns
+/// can be referenced through the `t` alias (e.g. `md5(t.id::text || t.amount::text)`).
+#[derive(Clone)]
+pub struct TableHashExpression(String);
+
+impl TableHashExpression {
+    pub const ROW_PLACEHOLDER: &'static str = "{row}";
+
+    pub fn new(template: impl Into<String>) -> Self {
+        Self(template.into())
+    }
+
+    pub fn template(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the expression with the row placeholder expanded.
+    pub fn expression(&self) -> String {
+        self.0.replace(Self::ROW_PLACEHOLDER, "(t.*)")
+    }
+}
+
+impl Default for TableHashExpression {
+    fn default() -> Self {
+        Self::new("md5({row}::varchar)")
+    }
+}
+
+/// Represents a glob pattern (e.g. `*_at`, `updated_*`) matched against column names.
+///
+/// `*` matches any sequence of characters and `?` matches a single character.
+#[derive(Clone)]
+pub struct ColumnPattern(String);
+
+impl ColumnPattern {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self(pattern.into())
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.0
+    }
+
+    /// Determines whether the column name matches the pattern.
+    pub fn matches(&self, column_name: &str) -> bool {
+        let pattern = self.0.chars().collect::<Vec<char>>();
+        let name = column_name.chars().collect::<Vec<char>>();
+
+        // Iterative glob matching, backtracking to the last `*` on a mismatch
+        let (mut p, mut n) = (0, 0);
+        let mut backtrack: Option<(usize, usize)> = None;
+
+        while n < name.len() {
+            if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+                p += 1;
+                n += 1;
+            } else if p < pattern.len() && pattern[p] == '*' {
+                backtrack = Some((p, n));
+                p += 1;
+            } else if let Some((star_p, star_n)) = backtrack {
+                p = star_p + 1;
+                n = star_n + 1;
+                backtrack = Some((star_p, star_n + 1));
+            } else {
+                return false;
+            }
+        }
+
+        pattern[p..].iter().all(|c| *c == '*')
+    }
+}
+
+/// Represents the inclusive range of values of an integer key of a table.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone, Copy)]
+pub struct TableKeyRange {
+    min: i64,
+    max: i64,
+}
+
+impl TableKeyRange {
+    pub fn new(min: i64, max: i64) -> Self {
+        Self { min, max }
+    }
+
+    pub fn min(&self) -> i64 {
+        self.min
+    }
+
+    pub fn max(&self) -> i64 {
+        self.max
+    }
+
+    /// Returns the smallest range covering both ranges.
+    pub fn union(&self, other: &TableKeyRange) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Returns the middle `percentage` percent of the range, leaving equal margins
+    /// at its head and its tail.
+    pub fn middle(&self, percentage: u64) -> Self {
+        let span = self.max as i128 - self.min as i128;
+        let width = span * percentage.min(100) as i128 / 100;
+        let lower = self.min as i128 + (span - width) / 2;
+
+        Self::new(lower as i64, (lower + width) as i64)
+    }
+
+    /// Returns the condition keeping only the rows whose key falls within the range.
+    pub fn condition(&self, key: &str) -> String {
+        format!("{} BETWEEN {} AND {}", key, self.min, self.max)
+    }
+}
+
+/// Represents a temporal predicate selecting the snapshot of a table
+/// as it was at a given point in time.
+pub struct TableTemporalPredicate {
+    column: String,
+    end_column: Option<String>,
+    as_of: String,
+}
+
+impl TableTemporalPredicate {
+    pub fn new(column: impl Into<String>, as_of: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            end_column: None,
+            as_of: as_of.into(),
+        }
+    }
+
+    /// Bounds the validity of each row version by the given column, `NULL` while the version is current.
+    pub fn with_end_column(mut self, end_column: Option<impl Into<String>>) -> Self {
+        self.end_column = end_column.map(Into::into);
+        self
+    }
+
+    /// Returns the condition keeping only rows that were valid at the `as_of` timestamp.
+    ///
+    /// Without an end column, this is every row created by then, including superseded versions.
+    pub fn condition(&self) -> String {
+        let as_of = self.as_of.replace('\'', "''");
+        let created = format!("{} <= '{}'", quote_identifier(&self.column), as_of);
+
+        match &self.end_column {
+            Some(end_column) => {
+                let end_column = quote_identifier(end_column);
+                format!(
+                    "{} AND ({} IS NULL OR {} > '{}')",
+                    created, end_column, end_column, as_of
+                )
+            }
+            None => created,
+        }
+    }
+}
+
+/// Represents the predicate of the incremental mode, selecting the rows
+/// whose tracking column changed since a given point in time.
+pub struct TableChangedSincePredicate {
+    column: String,
+    since: String,
+}
+
+impl TableChangedSincePredicate {
+    pub fn new(column: impl Into<String>, since: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            since: since.into(),
+        }
+    }
+
+    /// Returns the condition keeping only rows changed at or after the `since` timestamp.
+    pub fn condition(&self) -> String {
+        format!(
+            "{} >= '{}'",
+            quote_identifier(&self.column),
+            self.since.replace('\'', "''")
+        )
+    }
+}
+
+/// Represents an entry of the included or excluded tables.
+///
+/// Entries containing `*`, `?` or `%` are wildcard patterns, entries prefixed with `~`
+/// are regular expressions, and any other entry is a literal table name.
+pub enum TableNamePattern {
+    Literal(String),
+    Wildcard(String),
+    Regex(String),
+}
+
+impl TableNamePattern {
+    pub fn new(entry: &str) -> Self {
+        if let Some(regex) = entry.strip_prefix('~') {
+            Self::Regex(regex.to_string())
+        } else if entry.contains(['*', '?', '%']) {
+            Self::Wildcard(entry.to_string())
+        } else {
+            Self::Literal(entry.to_string())
+        }
+    }
+
+    /// Returns the condition matching the table names of a pattern, or `None` for a literal name.
+    ///
+    /// Wildcards are translated to a `LIKE` pattern, where `*` and `%` match any sequence
+    /// of characters and `?` matches a single character, while `_` is matched literally.
+    pub fn condition(&self, negated: bool) -> Option<String> {
+        let not = if negated { "NOT " } else { "" };
+
+        match self {
+            Self::Literal(_) => None,
+            Self::Wildcard(pattern) => {
+                let like_pattern = pattern
+                    .replace('\\', "\\\\")
+                    .replace('_', "\\_")
+                    .replace('*', "%")
+                    .replace('?', "_");
+                Some(format!(
+                    "table_name {}LIKE '{}'",
+                    not,
+                    escape_literal(&like_pattern)
+                ))
+            }
+            Self::Regex(regex) => {
+                let operator = if negated { "!~" } else { "~" };
+                Some(format!(
+                    "table_name {} '{}'",
+                    operator,
+                    escape_literal(regex)
+                ))
+            }
+        }
+    }
+}
+
+/// Escapes the single quotes of a value used in an SQL string literal.
+fn escape_literal(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
 pub struct IncludedExcludedTables {
     included_tables: Vec<String>,
     excluded_tables: Vec<String>,
@@ -58,6 +679,8 @@ pub struct IncludedExcludedTables {
 pub enum TableMode {
     Include,
     Exclude,
+    /// The included tables, minus the excluded ones.
+    IncludeExclude,
 }
 
 impl IncludedExcludedTables {
@@ -65,10 +688,6 @@ impl IncludedExcludedTables {
         include_tables: Vec<impl Into<String>>,
         exclude_tables: Vec<impl Into<String>>,
     ) -> Self {
-        if !include_tables.is_empty() && !exclude_tables.is_empty() {
-            panic!("Cannot include and exclude tables at the same time");
-        }
-
         Self {
             included_tables: include_tables.into_iter().map(|t| t.into()).collect(),
             excluded_tables: exclude_tables.into_iter().map(|t| t.into()).collect(),
@@ -76,7 +695,9 @@ impl IncludedExcludedTables {
     }
 
     pub fn table_mode(&self) -> Option<TableMode> {
-        if self.has_included_tables() {
+        if self.has_included_tables() && self.has_excluded_tables() {
+            Some(TableMode::IncludeExclude)
+        } else if self.has_included_tables() {
             Some(TableMode::Include)
         } else if self.has_excluded_tables() {
             Some(TableMode::Exclude)
@@ -85,34 +706,74 @@ impl IncludedExcludedTables {
         }
     }
 
+    /// Returns the statements filtering the table names, according to the mode of the tables.
+    pub fn statement(&self) -> String {
+        match self.table_mode() {
+            None => "".to_string(),
+            Some(TableMode::Include) => self.inclusion_statement(),
+            Some(TableMode::Exclude) => self.exclusion_statement(),
+            Some(TableMode::IncludeExclude) => format!(
+                "{} {}",
+                self.inclusion_statement(),
+                self.exclusion_statement()
+            ),
+        }
+    }
+
+    /// Returns the statement excluding the literal table names and every table matching a pattern.
     pub fn exclusion_statement(&self) -> String {
         if !self.has_excluded_tables() {
             return String::new();
         }
 
-        let joined_tables = self
-            .excluded_tables
-            .iter()
-            .map(|table| format!("'{}'", table))
-            .collect::<Vec<String>>()
-            .join(",");
+        let conditions = Self::conditions(&self.excluded_tables, true);
 
-        format!("AND table_name NOT IN ({})", joined_tables)
+        format!("AND {}", conditions.join(" AND "))
     }
 
+    /// Returns the statement keeping the literal table names and every table matching a pattern.
     pub fn inclusion_statement(&self) -> String {
         if !self.has_included_tables() {
             return String::new();
         }
 
-        let joined_tables = self
-            .included_tables
+        let conditions = Self::conditions(&self.included_tables, false);
+
+        match conditions.as_slice() {
+            [condition] => format!("AND {}", condition),
+            _ => format!("AND ({})", conditions.join(" OR ")),
+        }
+    }
+
+    /// Builds a single `IN` condition for the literal table names, followed by a condition per pattern.
+    fn conditions(tables: &[String], negated: bool) -> Vec<String> {
+        let table_name_patterns = tables
             .iter()
-            .map(|table| format!("'{}'", table))
-            .collect::<Vec<String>>()
-            .join(",");
+            .map(|table| TableNamePattern::new(table))
+            .collect::<Vec<TableNamePattern>>();
 
-        format!("AND table_name IN ({})", joined_tables)
+        let literals = table_name_patterns
+            .iter()
+            .filter_map(|pattern| match pattern {
+                TableNamePattern::Literal(table) => Some(format!("'{}'", escape_literal(table))),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+
+        let mut conditions = Vec::new();
+
+        if !literals.is_empty() {
+            let operator = if negated { "NOT IN" } else { "IN" };
+            conditions.push(format!("table_name {} ({})", operator, literals.join(",")));
+        }
+
+        conditions.extend(
+            table_name_patterns
+                .iter()
+                .filter_map(|pattern| pattern.condition(negated)),
+        );
+
+        conditions
     }
 
     fn has_included_tables(&self) -> bool {
@@ -124,6 +785,46 @@ impl IncludedExcludedTables {
     }
 }
 
+/// The types of relations listed as tables, as named by `information_schema.tables.table_type`.
+///
+/// Base tables are always listed, while other types (e.g. the `FOREIGN` tables of `postgres_fdw`)
+/// are only listed when opted in, since their rows may not be hashed like local ones.
+#[derive(Clone, Default)]
+pub struct TableTypes(Vec<String>);
+
+impl TableTypes {
+    /// The type of the regular tables of a schema.
+    pub const BASE_TABLE: &'static str = "BASE TABLE";
+
+    /// Creates the table types listing base tables along with the given types, matched regardless of their case.
+    pub fn new(included_types: Vec<impl Into<String>>) -> Self {
+        let mut table_types = vec![Self::BASE_TABLE.to_string()];
+        for table_type in included_types {
+            let table_type = table_type.into().trim().to_uppercase();
+            if !table_types.contains(&table_type) {
+                table_types.push(table_type);
+            }
+        }
+        Self(table_types)
+    }
+
+    /// Returns the statement keeping the tables of these types only.
+    pub fn statement(&self) -> String {
+        match self.0.as_slice() {
+            [] => format!("AND table_type = '{}'", Self::BASE_TABLE),
+            [table_type] => format!("AND table_type = '{}'", escape_literal(table_type)),
+            table_types => format!(
+                "AND table_type IN ({})",
+                table_types
+                    .iter()
+                    .map(|table_type| format!("'{}'", escape_literal(table_type)))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -175,10 +876,390 @@ mod tests {
     }
 
     #[test]
-    #[should_panic = "Cannot include and exclude tables at the same time"]
+    fn test_table_column_nullable_keys() {
+        let columns = vec![
+            TableColumn::new("id", "integer", false, None),
+            TableColumn::new("email", "text", true, None),
+            TableColumn::new("tenant_id", "integer", true, None),
+        ];
+        let keys = vec!["id".to_string(), "email".to_string()];
+
+        assert_eq!(TableColumn::nullable_keys(&keys, &columns), vec!["email"]);
+        assert!(TableColumn::nullable_keys(&keys[..1], &columns).is_empty());
+    }
+
+    #[test]
+    fn test_table_column_collation_mismatches() {
+        let keys = vec!["tenant".to_string(), "code".to_string(), "id".to_string()];
+        let columns = |code_collation: &str| {
+            vec![
+                TableColumn::new("tenant", "text", false, None)
+                    .with_collation(Some("en_US.UTF-8".to_string())),
+                TableColumn::new("code", "text", false, None)
+                    .with_collation(Some(code_collation.to_string())),
+                TableColumn::new("id", "integer", false, None),
+            ]
+        };
+
+        assert_eq!(
+            TableColumn::collation_mismatches(&keys, &columns("en_US.UTF-8"), &columns("C")),
+            vec!["code"]
+        );
+        assert!(TableColumn::collation_mismatches(
+            &keys,
+            &columns("en_US.UTF-8"),
+            &columns("en_US.UTF-8")
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_table_primary_keys_keyset() {
+        let keys = TablePrimaryKeys::from_columns(vec!["id".to_string(), "Tenant".to_string()]);
+        let cursor = TableKeyCursor::new("'42', 'acme'");
+
+        assert!(keys.is_keyset());
+        assert!(!keys.without_keyset().is_keyset());
+        assert!(!TablePrimaryKeys::new("created_at, id").is_keyset());
+        assert_eq!(
+            keys.cursor_expression(),
+            r#"concat_ws(', ', quote_nullable("id"), quote_nullable("Tenant"))"#
+        );
+        assert_eq!(keys.after(&cursor), r#"("id", "Tenant") > ('42', 'acme')"#);
+    }
+
+    #[test]
+    fn test_table_primary_keys_with_binary_collation() {
+        let keys = TablePrimaryKeys::from_columns(vec!["id".to_string(), "Tenant".to_string()])
+            .with_binary_collation(&["Tenant".to_string()]);
+        let cursor = TableKeyCursor::new("'42', 'acme'");
+
+        assert!(keys.is_keyset());
+        assert_eq!(keys.keys(), r#""id", "Tenant" COLLATE "C""#);
+        assert_eq!(
+            keys.after(&cursor),
+            r#"("id", "Tenant" COLLATE "C") > ('42', 'acme')"#
+        );
+        assert_eq!(
+            TablePrimaryKeys::new("created_at")
+                .with_binary_collation(&["created_at".to_string()])
+                .keys(),
+            "created_at"
+        );
+    }
+
+    #[test]
+    fn test_table_primary_keys_is_well_formed() {
+        let keys = |columns: &[&str]| columns.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+
+        assert!(TablePrimaryKeys::is_well_formed(&keys(&["id", "Tenant"])));
+        assert!(!TablePrimaryKeys::is_well_formed(&keys(&[])));
+        assert!(!TablePrimaryKeys::is_well_formed(&keys(&["id", ""])));
+        assert!(!TablePrimaryKeys::is_well_formed(&keys(&[
+            "id",
+            "........pg.dropped.3........"
+        ])));
+    }
+
+    #[test]
+    fn test_table_primary_keys_missing_from() {
+        let keys = vec!["id".to_string(), "Tenant".to_string()];
+
+        assert!(
+            TablePrimaryKeys::missing_from(r#"created_at DESC, "Tenant", ID"#, &keys).is_empty()
+        );
+        assert_eq!(
+            TablePrimaryKeys::missing_from("created_at, id", &keys),
+            vec!["Tenant"]
+        );
+        assert_eq!(
+            TablePrimaryKeys::missing_from("created_at", &keys),
+            vec!["id", "Tenant"]
+        );
+    }
+
+    #[test]
+    fn test_table_primary_keys_from_columns() {
+        let primary_keys = TablePrimaryKeys::from_columns(vec![
+            "tenant_id".to_string(),
+            "Order_Id".to_string(),
+            "tenant_id".to_string(),
+        ]);
+
+        assert_eq!(primary_keys.keys(), r#""tenant_id", "Order_Id""#);
+        assert_eq!(
+            TablePrimaryKeys::from_columns(vec![r#"weird"key"#.to_string()]).keys(),
+            r#""weird""key""#
+        );
+    }
+
+    #[test]
+    fn test_table_projection_is_alphabetical() {
+        let first_projection = TableProjection::new(vec!["name", "id", "Created_At"]);
+        let second_projection = TableProjection::new(vec!["id", "Created_At", "name"]);
+
+        assert_eq!(
+            first_projection.select_list(),
+            r#""Created_At", "id", "name""#
+        );
+        assert_eq!(
+            first_projection.select_list(),
+            second_projection.select_list()
+        );
+        assert_eq!(TableProjection::default().select_list(), "*");
+    }
+
+    #[test]
+    fn test_table_projection_with_coalesced_columns() {
+        let projection =
+            TableProjection::new(vec!["name", "id", "email"]).with_coalesced_columns(vec!["name"]);
+
+        assert_eq!(
+            projection.select_list(),
+            r#""email", "id", COALESCE("name", '') AS "name""#
+        );
+    }
+
+    #[test]
+    fn test_table_column_is_text() {
+        assert!(TableColumn::new("name", "text", true, None).is_text());
+        assert!(TableColumn::new("code", "character varying", true, None).is_text());
+        assert!(TableColumn::new("flag", "character", true, None).is_text());
+        assert!(!TableColumn::new("id", "integer", false, None).is_text());
+    }
+
+    #[test]
+    fn test_table_hash_expression() {
+        let default_expression = TableHashExpression::default();
+        let custom_expression = TableHashExpression::new("md5(row_to_json({row})::text)");
+
+        assert_eq!(default_expression.expression(), "md5((t.*)::varchar)");
+        assert_eq!(
+            custom_expression.expression(),
+            "md5(row_to_json((t.*))::text)"
+        );
+    }
+
+    #[test]
+    fn test_column_pattern_matches() {
+        let suffix_pattern = ColumnPattern::new("*_at");
+        let prefix_pattern = ColumnPattern::new("updated_*");
+        let single_char_pattern = ColumnPattern::new("col?");
+
+        assert!(suffix_pattern.matches("created_at"));
+        assert!(suffix_pattern.matches("_at"));
+        assert!(!suffix_pattern.matches("created_by"));
+        assert!(prefix_pattern.matches("updated_by"));
+        assert!(!prefix_pattern.matches("last_updated_by"));
+        assert!(single_char_pattern.matches("col1"));
+        assert!(!single_char_pattern.matches("col12"));
+    }
+
+    #[test]
+    fn test_table_filter_where_clause() {
+        let empty_filter = TableFilter::default();
+        let filter = TableFilter::new(vec!["a > 1", "b IS NOT NULL"]);
+
+        assert_eq!(empty_filter.where_clause(), "");
+        assert_eq!(filter.where_clause(), " WHERE (a > 1) AND (b IS NOT NULL)");
+    }
+
+    #[test]
+    fn test_table_sample_clause() {
+        assert_eq!(TableSample::default().clause(), "");
+        assert_eq!(
+            TableSample::new(5, 42).clause(),
+            " TABLESAMPLE SYSTEM (5) REPEATABLE (42)"
+        );
+    }
+
+    #[test]
+    fn test_table_filter_and() {
+        let filter = TableFilter::new(vec!["a > 1"]).and("id BETWEEN 1 AND 10");
+
+        assert_eq!(
+            filter.where_clause(),
+            " WHERE (a > 1) AND (id BETWEEN 1 AND 10)"
+        );
+    }
+
+    #[test]
+    fn test_table_key_range_middle() {
+        let key_range = TableKeyRange::new(0, 100);
+
+        assert_eq!(key_range.middle(50), TableKeyRange::new(25, 75));
+        assert_eq!(key_range.middle(100), key_range);
+        assert_eq!(
+            TableKeyRange::new(1, 1000).middle(10),
+            TableKeyRange::new(451, 550)
+        );
+        assert_eq!(
+            TableKeyRange::new(i64::MIN, i64::MAX).middle(100),
+            TableKeyRange::new(i64::MIN, i64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_table_key_range_union() {
+        let first = TableKeyRange::new(10, 100);
+        let second = TableKeyRange::new(1, 50);
+
+        assert_eq!(first.union(&second), TableKeyRange::new(1, 100));
+    }
+
+    #[test]
+    fn test_table_key_range_condition() {
+        let key_range = TableKeyRange::new(25, 75);
+
+        assert_eq!(key_range.condition("id"), "id BETWEEN 25 AND 75");
+    }
+
+    #[test]
+    fn test_temporal_predicate_condition() {
+        let predicate = TableTemporalPredicate::new("valid_from", "2024-01-01 00:00:00");
+
+        assert_eq!(
+            predicate.condition(),
+            r#""valid_from" <= '2024-01-01 00:00:00'"#
+        );
+    }
+
+    #[test]
+    fn test_temporal_predicate_condition_with_end_column() {
+        let predicate = TableTemporalPredicate::new("validFrom", "2024-01-01 00:00:00")
+            .with_end_column(Some("validTo"));
+
+        assert_eq!(
+            predicate.condition(),
+            r#""validFrom" <= '2024-01-01 00:00:00' AND ("validTo" IS NULL OR "validTo" > '2024-01-01 00:00:00')"#
+        );
+    }
+
+    #[test]
+    fn test_changed_since_predicate_condition() {
+        let predicate = TableChangedSincePredicate::new("updated_at", "2024-05-01 00:00:00");
+
+        assert_eq!(
+            predicate.condition(),
+            r#""updated_at" >= '2024-05-01 00:00:00'"#
+        );
+    }
+
+    #[test]
+    fn test_changed_since_predicate_quotes_mixed_case_column() {
+        let predicate = TableChangedSincePredicate::new("updatedAt", "2024-05-01 00:00:00");
+
+        assert_eq!(
+            predicate.condition(),
+            r#""updatedAt" >= '2024-05-01 00:00:00'"#
+        );
+    }
+
+    #[test]
+    fn test_table_name_pattern_condition() {
+        assert!(TableNamePattern::new("table1").condition(false).is_none());
+        assert_eq!(
+            TableNamePattern::new("audit_*").condition(false).unwrap(),
+            r"table_name LIKE 'audit\_%'"
+        );
+        assert_eq!(
+            TableNamePattern::new("log%_2024").condition(true).unwrap(),
+            r"table_name NOT LIKE 'log%\_2024'"
+        );
+        assert_eq!(
+            TableNamePattern::new("table?").condition(false).unwrap(),
+            "table_name LIKE 'table_'"
+        );
+        assert_eq!(
+            TableNamePattern::new("~^events_[0-9]+$")
+                .condition(false)
+                .unwrap(),
+            "table_name ~ '^events_[0-9]+$'"
+        );
+        assert_eq!(
+            TableNamePattern::new("~^tmp_").condition(true).unwrap(),
+            "table_name !~ '^tmp_'"
+        );
+    }
+
+    #[test]
+    fn test_included_tables_with_literals_and_patterns() {
+        let included_tables = vec!["table1", "audit_*", "table2", "~^events_"];
+        let excluded_tables: Vec<String> = vec![];
+        let included_excluded_tables =
+            IncludedExcludedTables::new(included_tables, excluded_tables);
+
+        assert_eq!(
+            included_excluded_tables.inclusion_statement(),
+            r"AND (table_name IN ('table1','table2') OR table_name LIKE 'audit\_%' OR table_name ~ '^events_')"
+        );
+    }
+
+    #[test]
+    fn test_excluded_tables_with_literals_and_patterns() {
+        let included_tables: Vec<String> = vec![];
+        let excluded_tables = vec!["table1", "tmp_*"];
+        let included_excluded_tables =
+            IncludedExcludedTables::new(included_tables, excluded_tables);
+
+        assert_eq!(
+            included_excluded_tables.exclusion_statement(),
+            r"AND table_name NOT IN ('table1') AND table_name NOT LIKE 'tmp\_%'"
+        );
+    }
+
+    #[test]
+    fn test_included_tables_with_only_a_pattern() {
+        let included_tables = vec!["audit_*"];
+        let excluded_tables: Vec<String> = vec![];
+        let included_excluded_tables =
+            IncludedExcludedTables::new(included_tables, excluded_tables);
+
+        assert_eq!(
+            included_excluded_tables.inclusion_statement(),
+            r"AND table_name LIKE 'audit\_%'"
+        );
+    }
+
+    #[test]
     fn test_when_included_tables_and_excluded_tables_are_both_not_empty() {
-        let included_tables: Vec<&str> = vec!["table1"];
+        let included_tables: Vec<&str> = vec!["table1", "table2"];
         let excluded_tables: Vec<&str> = vec!["table2"];
-        _ = IncludedExcludedTables::new(included_tables, excluded_tables);
+        let included_excluded_tables =
+            IncludedExcludedTables::new(included_tables, excluded_tables);
+
+        assert!(matches!(
+            included_excluded_tables.table_mode().unwrap(),
+            TableMode::IncludeExclude
+        ));
+
+        assert_eq!(
+            included_excluded_tables.inclusion_statement(),
+            "AND table_name IN ('table1','table2')"
+        );
+        assert_eq!(
+            included_excluded_tables.exclusion_statement(),
+            "AND table_name NOT IN ('table2')"
+        );
+    }
+
+    #[test]
+    fn test_table_types_statement_lists_base_tables_by_default() {
+        assert_eq!(
+            "AND table_type = 'BASE TABLE'",
+            TableTypes::new(Vec::<String>::new()).statement()
+        );
+        assert_eq!(
+            "AND table_type = 'BASE TABLE'",
+            TableTypes::default().statement()
+        );
+    }
+
+    #[test]
+    fn test_table_types_statement_with_included_types() {
+        assert_eq!(
+            "AND table_type IN ('BASE TABLE','FOREIGN')",
+            TableTypes::new(vec!["foreign", "BASE TABLE", " Foreign "]).statement()
+        );
     }
 }
@@ -1,26 +1,78 @@
+use crate::diff::checkpoint::Checkpoint;
 use crate::diff::diff_payload::DiffPayload;
+use crate::diff::fail_fast;
+use crate::diff::progress;
 use crate::diff::table::query::input::{
-    QueryHashDataInput, QueryPrimaryKeysInput, QueryTableCountInput, QueryTableNamesInput,
+    QueryAllPrimaryKeysInput, QueryCopyRowsInput, QueryHashDataInput, QueryKeyCursorInput,
+    QueryKeyRangeInput, QueryPrimaryKeysInput, QueryRowEstimatesInput, QueryTableColumnsInput,
+    QueryTableCountInput, QueryTableCountsInput, QueryTableNamesInput,
+};
+use crate::diff::table::query::output::{
+    TableCountDiff, TableDiffOutput, TableDrift, TableSource, TableTimings,
 };
-use crate::diff::table::query::output::{TableCountDiff, TableDiffOutput, TableSource};
 
 use crate::diff::table::query::table_query_executor::{
     TableDualSourceQueryExecutor, TableSingleSourceQueryExecutor,
 };
 use crate::diff::table::query::table_types::{
-    TableName, TableOffset, TablePosition, TablePrimaryKeys,
+    ColumnPattern, TableChangedSincePredicate, TableColumn, TableFilter, TableHashExpression,
+    TableKeyCursor, TableName, TableOffset, TablePosition, TablePrimaryKeys, TableProjection,
+    TableSample, TableTemporalPredicate,
 };
 use anyhow::Result;
 use colored::Colorize;
-use tracing::{debug, info};
+use futures::{Stream, StreamExt};
+use tracing::{debug, debug_span, info, info_span, warn, Instrument};
 
 use crate::diff::diff_output::DiffOutput;
-use crate::diff::types::SchemaName;
-use std::time::Instant;
+use crate::diff::types::{DiffOutputMarker, QueryTimeoutError, RelationNotFoundError, SchemaName};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+use tokio_util::sync::CancellationToken;
+
+/// The primary keys of the tables of a comparison, fetched for all of them at once when first needed,
+/// rather than with a query per table. They are keyed by schema and table name.
+struct PrimaryKeyCache {
+    schema_name: String,
+    table_names: Vec<String>,
+    primary_keys: OnceCell<HashMap<(String, String), Vec<String>>>,
+}
+
+impl PrimaryKeyCache {
+    fn new(schema_name: String, table_names: Vec<String>) -> Self {
+        Self {
+            schema_name,
+            table_names,
+            primary_keys: OnceCell::new(),
+        }
+    }
+}
+
+/// The row counts of the tables of a count-only comparison, fetched for all of them at once when
+/// first needed, rather than with a query per table. A database the batch failed for is `None`.
+struct TableCountCache {
+    schema_name: String,
+    table_names: Vec<(TableName, TableName)>,
+    #[allow(clippy::type_complexity)]
+    counts: OnceCell<(Option<HashMap<String, i64>>, Option<HashMap<String, i64>>)>,
+}
+
+impl TableCountCache {
+    fn new(schema_name: String, table_names: Vec<(TableName, TableName)>) -> Self {
+        Self {
+            schema_name,
+            table_names,
+            counts: OnceCell::new(),
+        }
+    }
+}
 
 pub struct TableDiffer<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor> {
     single_table_query_executor: TQE,
     dual_table_query_executor: DTQE,
+    cancellation_token: CancellationToken,
 }
 
 impl<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor>
@@ -30,164 +82,668 @@ impl<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor>
         Self {
             single_table_query_executor,
             dual_table_query_executor,
+            cancellation_token: CancellationToken::new(),
         }
     }
 
-    pub async fn diff_all_table_data(&self, diff_payload: &DiffPayload) -> Result<Vec<DiffOutput>> {
+    /// Stops comparing tables once the given token is cancelled, keeping the outputs of the tables
+    /// compared until then.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = cancellation_token;
+        self
+    }
+
+    pub async fn diff_all_table_data(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: String,
+    ) -> Result<Vec<DiffOutput>> {
         info!("{}", "Starting data analysis…".yellow().bold());
 
-        let mut tables = self.get_all_tables(diff_payload).await?;
+        let checkpoint = Self::checkpoint(diff_payload)?;
+        let tables = self
+            .get_sorted_tables(diff_payload, schema_name.to_owned())
+            .await?;
+        let tables = Self::pending_tables(tables, &schema_name, checkpoint.as_ref());
+        let total_tables = tables.len();
 
-        tables.sort_by_key(|s| s.to_lowercase());
+        // Advance the overall progress as each table completes
+        let tables_bar = progress::tables_bar(diff_payload.progress(), total_tables);
+        let table_diffs = self
+            .table_diffs(diff_payload, schema_name.to_owned(), tables, checkpoint)
+            .inspect(|_| tables_bar.inc(1));
 
-        let sorted_tables = tables.to_owned();
+        info!(
+            "{}",
+            "Waiting for table analysis to complete…".yellow().bold()
+        );
+        let start = Instant::now();
+        let analysed_tables = fail_fast::collect_in_order(table_diffs).await;
+        tables_bar.finish_and_clear();
+        let elapsed = start.elapsed();
+        info!(
+            "{}",
+            format!(
+                "Total table analysis completed in: {}ms",
+                elapsed.as_millis()
+            )
+            .yellow()
+            .bold(),
+        );
+        info!(
+            "{}",
+            format!("Total tables for row count check: {}", total_tables)
+                .bright_blue()
+                .bold()
+        );
 
-        let futures = sorted_tables.iter().map(|table_name| async {
-            let start = Instant::now();
+        info!("##############################################");
+        info!("{}", "Table analysis results 👇".bright_magenta().bold());
 
-            // Start loading counts for table from both DBs
-            let query_count_input = QueryTableCountInput::new(
-                SchemaName::new(diff_payload.schema_name().to_string()),
-                TableName::new(table_name.to_string()),
-            );
+        // Leave out the results that match when only the differences are reported
+        for table_diff_result in analysed_tables
+            .iter()
+            .filter(|output| !diff_payload.only_differences() || output.is_difference())
+        {
+            info!("{}", table_diff_result.to_string());
+        }
 
-            let table_counts_start = Instant::now();
-            let (first_result, second_result) = self
-                .dual_table_query_executor
-                .query_table_count(query_count_input)
-                .await;
+        info!("##############################################");
 
-            let table_counts_elapsed = table_counts_start.elapsed();
-            debug!(
-                "Table counts for {} loaded in: {}ms",
-                table_name.clone(),
-                table_counts_elapsed.as_millis()
-            );
+        Ok(analysed_tables
+            .into_iter()
+            .map(|diff| diff.convert(SchemaName::new(schema_name.to_owned())))
+            .collect())
+    }
+
+    /// Compares the data of every table of the schema, yielding the output of each table as soon
+    /// as it is known (e.g. to render every verdict live), in the order the comparisons complete.
+    pub async fn diff_all_table_data_stream<'a>(
+        &'a self,
+        diff_payload: &'a DiffPayload,
+        schema_name: String,
+    ) -> Result<impl Stream<Item = DiffOutput> + 'a> {
+        let checkpoint = Self::checkpoint(diff_payload)?;
+        let tables = self
+            .get_sorted_tables(diff_payload, schema_name.to_owned())
+            .await?;
+        let tables = Self::pending_tables(tables, &schema_name, checkpoint.as_ref());
+
+        Ok(self
+            .table_diffs(diff_payload, schema_name.to_owned(), tables, checkpoint)
+            .map(move |(_, diff)| diff.convert(SchemaName::new(schema_name.to_owned()))))
+    }
+
+    /// Opens the checkpoint file to resume from, if any. Dry runs compare nothing, so leave it untouched.
+    fn checkpoint(diff_payload: &DiffPayload) -> Result<Option<Checkpoint>> {
+        diff_payload
+            .resume()
+            .filter(|_| !diff_payload.dry_run())
+            .map(Checkpoint::open)
+            .transpose()
+    }
+
+    /// Leaves out the tables of the schema recorded to the checkpoint, if any, by a previous run.
+    fn pending_tables(
+        tables: Vec<(String, Option<TableSource>, bool)>,
+        schema_name: &str,
+        checkpoint: Option<&Checkpoint>,
+    ) -> Vec<(String, Option<TableSource>, bool)> {
+        let Some(checkpoint) = checkpoint else {
+            return tables;
+        };
+
+        let (completed, pending): (Vec<_>, Vec<_>) = tables
+            .into_iter()
+            .partition(|(table_name, _, _)| checkpoint.is_completed(schema_name, table_name));
 
+        if !completed.is_empty() {
             info!(
-                "{}",
-                format!("Analyzing table: {}", table_name.clone())
-                    .yellow()
-                    .bold()
+                "Skipping {} tables of {} already compared according to the checkpoint",
+                completed.len(),
+                schema_name
             );
+        }
 
-            // Start counts comparison
-            let table_diff_result = Self::extract_result(table_name, first_result, second_result);
+        pending
+    }
 
-            let elapsed = start.elapsed();
-            debug!(
-                "{}",
-                format!("Table analysis completed in: {}ms", elapsed.as_millis())
-            );
+    /// Returns the tables of the schema in either database, sorted by name, along with the source
+    /// each one is missing from, if any, so that such tables are reported without being counted,
+    /// and whether each one is a view (if views are compared as well).
+    async fn get_sorted_tables(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: String,
+    ) -> Result<Vec<(String, Option<TableSource>, bool)>> {
+        let mut first_tables = self
+            .get_all_tables(diff_payload, schema_name.to_owned())
+            .await?;
 
-            debug!("##############################################");
+        let views = self
+            .get_all_views(diff_payload, schema_name.to_owned())
+            .await
+            .into_iter()
+            .collect::<HashSet<String>>();
+        first_tables.extend(views.iter().cloned());
 
-            // If we only care about counts, return the result
-            if diff_payload.only_count() {
-                return table_diff_result;
-            }
+        // Tables may have been renamed in the second database
+        let second_names = |tables: &Vec<String>| {
+            tables
+                .iter()
+                .map(|table| diff_payload.second_table_name(table).to_string())
+                .collect::<Vec<String>>()
+        };
+        let input = QueryTableNamesInput::new(
+            SchemaName::new(schema_name.to_owned()),
+            second_names(diff_payload.included_tables()),
+            second_names(diff_payload.excluded_tables()),
+        )
+        .with_views(diff_payload.include_views())
+        .with_table_types(diff_payload.include_table_types().to_vec());
 
-            // If the diff result permits us to skip data comparison, return the result
-            if table_diff_result.skip_table_diff() {
-                return table_diff_result;
+        let tables = match self
+            .dual_table_query_executor
+            .query_second_table_names(input)
+            .await
+        {
+            Ok(second_tables) => Self::table_presence(diff_payload, first_tables, second_tables),
+            Err(e) => {
+                warn!(
+                    "Could not list the tables of the second database, so missing tables are only detected when counting their rows: {}",
+                    e
+                );
+                first_tables
+                    .into_iter()
+                    .map(|table| (table, None))
+                    .collect()
             }
+        };
 
-            let query_primary_keys_input = QueryPrimaryKeysInput::new(table_name.clone());
+        let mut tables = tables
+            .into_iter()
+            .map(|(table, missing_source)| {
+                let is_view = views.contains(&table);
+                (table, missing_source, is_view)
+            })
+            .collect::<Vec<(String, Option<TableSource>, bool)>>();
 
-            let primary_keys = self
-                .single_table_query_executor
-                .query_primary_keys(query_primary_keys_input)
-                .await;
+        // Sizes are only estimated when ordering by them
+        let row_estimates = if diff_payload.table_order().is_by_size() {
+            let input = QueryRowEstimatesInput::new(SchemaName::new(schema_name));
+            self.single_table_query_executor
+                .query_row_estimates(input)
+                .await
+        } else {
+            HashMap::new()
+        };
+        diff_payload.table_order().sort(
+            &mut tables,
+            |(table, _, _)| table.as_str(),
+            &row_estimates,
+        );
 
-            // If no primary keys found, return the result
-            if primary_keys.is_empty() {
-                let table_diff_result = TableDiffOutput::NoPrimaryKeyFound(table_name.clone());
-                return table_diff_result;
-            }
+        Ok(tables)
+    }
 
-            // Prepare the primary keys for the table
-            // Will be used for query ordering when hashing data
-            let primary_keys = primary_keys.as_slice().join(",");
+    /// Pairs every table of either database with the source it is missing from, if any.
+    /// Tables only found in the second database are named as they are there.
+    fn table_presence(
+        diff_payload: &DiffPayload,
+        first_tables: Vec<String>,
+        second_tables: Vec<String>,
+    ) -> Vec<(String, Option<TableSource>)> {
+        let mapped_tables = first_tables
+            .iter()
+            .map(|table| diff_payload.second_table_name(table))
+            .collect::<HashSet<&str>>();
 
-            let total_rows = match table_diff_result {
-                TableDiffOutput::NoCountDiff(_, rows) => rows,
-                _ => {
-                    // Since we do not expect to reach here, print the result and panic
-                    panic!("Unexpected table diff result")
-                }
-            };
+        let second_only_tables = second_tables
+            .iter()
+            .filter(|table| !mapped_tables.contains(table.as_str()))
+            .map(|table| (table.to_owned(), Some(TableSource::First(None))))
+            .collect::<Vec<(String, Option<TableSource>)>>();
 
-            let schema_name = SchemaName::new(diff_payload.schema_name().to_string());
-            let query_table_name = TableName::new(table_name.clone());
-            let table_offset = TableOffset::new(diff_payload.chunk_size());
-            let table_primary_keys = TablePrimaryKeys::new(primary_keys);
+        let second_tables = second_tables.into_iter().collect::<HashSet<String>>();
 
-            let start = Instant::now();
+        first_tables
+            .into_iter()
+            .map(|table| {
+                let missing_source = (!second_tables
+                    .contains(diff_payload.second_table_name(&table)))
+                .then_some(TableSource::Second(None));
+                (table, missing_source)
+            })
+            .chain(second_only_tables)
+            .collect()
+    }
 
-            if let Some(value) = self
-                .diff_table_data(
-                    diff_payload,
-                    schema_name,
-                    query_table_name,
-                    table_offset,
-                    table_primary_keys,
-                    total_rows,
-                    start,
-                )
-                .await
-            {
-                return value;
+    /// Compares the tables concurrently (up to `max_concurrency` at a time), yielding the output of each along with the position of the table.
+    /// The verdict of each table is recorded to the checkpoint, if any, as soon as it is known.
+    fn table_diffs<'a>(
+        &'a self,
+        diff_payload: &'a DiffPayload,
+        schema_name: String,
+        tables: Vec<(String, Option<TableSource>, bool)>,
+        checkpoint: Option<Checkpoint>,
+    ) -> impl Stream<Item = (usize, TableDiffOutput)> + 'a {
+        let checkpoint_schema_name = schema_name.to_owned();
+        let (first_filter, second_filter) = Self::table_filters(diff_payload);
+
+        // Only the tables found in both databases are compared, and so need their keys
+        let primary_key_cache = Arc::new(PrimaryKeyCache::new(
+            schema_name.to_owned(),
+            tables
+                .iter()
+                .filter(|(_, missing_source, _)| missing_source.is_none())
+                .map(|(table_name, _, _)| table_name.to_owned())
+                .collect(),
+        ));
+
+        // Counts are only batched when nothing but counts is compared, and they are exact
+        let batch_counts = diff_payload.only_count() && !diff_payload.estimate_counts();
+        let table_count_cache = Arc::new(TableCountCache::new(
+            schema_name.to_owned(),
+            tables
+                .iter()
+                .filter(|(_, missing_source, _)| batch_counts && missing_source.is_none())
+                .map(|(table_name, _, _)| {
+                    (
+                        TableName::new(table_name.as_str()),
+                        TableName::new(diff_payload.second_table_name(table_name)),
+                    )
+                })
+                .collect(),
+        ));
+
+        let futures = tables
+            .into_iter()
+            .map(move |(table_name, missing_source, is_view)| {
+                // Emit the logs of each table within its span, carrying the schema and table names
+                let table_span = info_span!("table", schema = %schema_name, name = %table_name);
+                let schema_name = schema_name.to_owned();
+                let first_filter = first_filter.clone();
+                let second_filter = second_filter.clone();
+                let primary_key_cache = primary_key_cache.clone();
+                let table_count_cache = table_count_cache.clone();
+
+                async move {
+                    // Tables missing from either database have nothing to compare
+                    let table_diff = match missing_source {
+                        Some(source) => TableDiffOutput::NotExists(table_name.to_owned(), source),
+                        None => {
+                            self.diff_table(
+                                diff_payload,
+                                &schema_name,
+                                &table_name,
+                                &first_filter,
+                                &second_filter,
+                                &primary_key_cache,
+                                &table_count_cache,
+                            )
+                            .await
+                        }
+                    };
+
+                    // Report views distinctly from the tables
+                    let table_diff = if is_view {
+                        TableDiffOutput::View(Box::new(table_diff))
+                    } else {
+                        table_diff
+                    };
+
+                    table_diff.with_source_aliases(
+                        diff_payload.first_alias(),
+                        diff_payload.second_alias(),
+                    )
+                }
+                .instrument(table_span)
+            });
+
+        fail_fast::until_difference(
+            futures,
+            diff_payload
+                .max_concurrency()
+                .map(|max_concurrency| max_concurrency as usize),
+            diff_payload.fail_fast(),
+            TableDiffOutput::is_difference,
+        )
+        .take_until(self.cancellation_token.cancelled())
+        .inspect(move |(_, table_diff)| {
+            if let Some(checkpoint) = &checkpoint {
+                if let Err(e) = checkpoint.record(&checkpoint_schema_name, table_diff) {
+                    warn!("{}", e);
+                }
             }
+        })
+    }
 
-            let elapsed = start.elapsed();
+    #[allow(clippy::too_many_arguments)]
+    async fn diff_table(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: &str,
+        table_name: &str,
+        first_filter: &TableFilter,
+        second_filter: &TableFilter,
+        primary_key_cache: &PrimaryKeyCache,
+        table_count_cache: &TableCountCache,
+    ) -> TableDiffOutput {
+        let start = Instant::now();
 
-            TableDiffOutput::NoDiffWithDuration(table_name.clone(), elapsed)
-        });
+        // The table may have been renamed in the second database
+        let second_table_name = TableName::new(diff_payload.second_table_name(table_name));
 
-        info!(
-            "{}",
-            "Waiting for table analysis to complete…".yellow().bold()
+        if let Some(column_error) = self
+            .changed_since_column_error(diff_payload, schema_name, table_name, &second_table_name)
+            .await
+        {
+            return column_error;
+        }
+
+        // Start loading counts for table from both DBs
+        let query_count_input = QueryTableCountInput::new(
+            SchemaName::new(schema_name.to_owned()),
+            TableName::new(table_name.to_string()),
+            first_filter.clone(),
+            second_filter.clone(),
+        )
+        .with_second_table_name(second_table_name.clone())
+        .with_estimate(diff_payload.estimate_counts());
+
+        let table_counts_start = Instant::now();
+        let (first_result, second_result) = self
+            .table_counts(
+                table_name,
+                query_count_input,
+                first_filter,
+                second_filter,
+                table_count_cache,
+            )
+            .await;
+
+        let table_counts_elapsed = table_counts_start.elapsed();
+        debug!(
+            "Table counts for {} loaded in: {}ms",
+            table_name,
+            table_counts_elapsed.as_millis()
         );
-        let start = Instant::now();
-        let analysed_tables = futures::future::join_all(futures).await;
-        let elapsed = start.elapsed();
+
         info!(
             "{}",
-            format!(
-                "Total table analysis completed in: {}ms",
-                elapsed.as_millis()
-            )
-            .yellow()
-            .bold(),
+            format!("Analyzing table: {}", table_name).yellow().bold()
         );
-        info!(
+
+        // Start counts comparison, reporting estimates as such
+        let table_diff_result = match (first_result, second_result) {
+            (Ok(first_rows), Ok(second_rows)) if diff_payload.estimate_counts() => {
+                TableDiffOutput::EstimatedCount(table_name.to_string(), first_rows, second_rows)
+            }
+            (first_result, second_result) => {
+                Self::extract_result(table_name, first_result, second_result)
+            }
+        };
+
+        let elapsed = start.elapsed();
+        debug!(
             "{}",
-            format!("Total tables for row count check: {}", tables.len())
-                .bright_blue()
-                .bold()
+            format!("Table analysis completed in: {}ms", elapsed.as_millis())
         );
 
-        info!("##############################################");
-        info!("{}", "Table analysis results 👇".bright_magenta().bold());
+        debug!("##############################################");
 
-        for table_diff_result in &analysed_tables {
-            info!("{}", table_diff_result.to_string());
+        // If we only care about counts, return the result
+        if diff_payload.only_count() {
+            return table_diff_result;
         }
 
-        info!("##############################################");
+        // Sample the common rows of tables whose counts differ, if requested, to tell how they drifted
+        if let (TableDiffOutput::Diff(_, count_diff), Some(samples)) =
+            (&table_diff_result, diff_payload.count_diff_samples())
+        {
+            let drift = self
+                .count_drift(
+                    diff_payload,
+                    schema_name,
+                    table_name,
+                    count_diff,
+                    samples,
+                    first_filter,
+                    second_filter,
+                    primary_key_cache,
+                )
+                .await;
 
-        Ok(analysed_tables
-            .into_iter()
-            .map(|diff| diff.into())
-            .collect())
+            return TableDiffOutput::Diff(
+                table_name.to_string(),
+                count_diff.clone().with_drift(drift),
+            );
+        }
+
+        // If the diff result permits us to skip data comparison, return the result
+        if table_diff_result.skip_table_diff() {
+            return table_diff_result;
+        }
+
+        // If both tables are empty, there are no rows to hash
+        if let TableDiffOutput::NoCountDiff(_, 0) = table_diff_result {
+            return TableDiffOutput::NoDiffWithDuration(
+                table_name.to_string(),
+                TableTimings::new(table_counts_elapsed, Duration::ZERO),
+            );
+        }
+
+        let primary_keys = self
+            .table_keys(diff_payload, schema_name, table_name, primary_key_cache)
+            .await;
+
+        // If no primary (or unique) keys found, nor an ordering given for the table (e.g. a view), return the result
+        if primary_keys.is_empty() && diff_payload.order_by_expression(table_name).is_none() {
+            let table_diff_result = TableDiffOutput::NoPrimaryKeyFound(table_name.to_string());
+            return table_diff_result;
+        }
+
+        let total_rows = match table_diff_result {
+            TableDiffOutput::NoCountDiff(_, rows) => rows,
+            _ => {
+                // Since we do not expect to reach here, print the result and panic
+                panic!("Unexpected table diff result")
+            }
+        };
+
+        // Restrict the comparison to the middle of the key range, if requested
+        let key_range_filters = match diff_payload.key_range_percentage() {
+            Some(percentage) if !primary_keys.is_empty() => {
+                self.key_range_filters(
+                    schema_name,
+                    table_name,
+                    &second_table_name,
+                    &primary_keys,
+                    percentage,
+                    first_filter,
+                    second_filter,
+                )
+                .await
+            }
+            _ => None,
+        };
+
+        let (first_filter, second_filter, total_rows) = match key_range_filters {
+            Some((first_filter, second_filter)) => {
+                // Count the rows within the range, since they determine the chunks to hash
+                let query_count_input = QueryTableCountInput::new(
+                    SchemaName::new(schema_name.to_owned()),
+                    TableName::new(table_name.to_string()),
+                    first_filter.clone(),
+                    second_filter.clone(),
+                )
+                .with_second_table_name(second_table_name.clone());
+
+                let (first_result, second_result) = self
+                    .dual_table_query_executor
+                    .query_table_count(query_count_input)
+                    .await;
+
+                match Self::extract_result(table_name, first_result, second_result) {
+                    TableDiffOutput::NoCountDiff(_, rows) => (first_filter, second_filter, rows),
+                    table_diff_result => return table_diff_result,
+                }
+            }
+            None => (first_filter.clone(), second_filter.clone(), total_rows),
+        };
+
+        // Hash only the sampled pages, if requested, whose rows determine the chunks to hash
+        let table_sample = Self::table_sample(diff_payload);
+        let total_rows = match table_sample.percent() {
+            Some(percent) => {
+                let query_count_input = QueryTableCountInput::new(
+                    SchemaName::new(schema_name.to_owned()),
+                    TableName::new(table_name.to_string()),
+                    first_filter.clone(),
+                    second_filter.clone(),
+                )
+                .with_second_table_name(second_table_name.clone())
+                .with_sample(table_sample.clone());
+
+                let (first_result, second_result) = self
+                    .dual_table_query_executor
+                    .query_table_count(query_count_input)
+                    .await;
+
+                match Self::extract_result(table_name, first_result, second_result) {
+                    TableDiffOutput::NoCountDiff(_, rows) => rows,
+                    table_diff_result => {
+                        return TableDiffOutput::Sampled(Box::new(table_diff_result), percent)
+                    }
+                }
+            }
+            None => total_rows,
+        };
+
+        // Hash the columns by name in a canonical order, so that their physical order does not matter
+        let query_table_columns_input = QueryTableColumnsInput::new(
+            SchemaName::new(schema_name.to_owned()),
+            TableName::new(table_name.to_string()),
+        )
+        .with_second_table_name(second_table_name.clone());
+
+        let (first_columns, second_columns) = self
+            .dual_table_query_executor
+            .query_table_columns(query_table_columns_input)
+            .await;
+
+        // Rows sharing NULL keys have no defined order, so windows may skip or repeat them
+        let mut has_nullable_keys = false;
+        if let Ok(columns) = &first_columns {
+            let nullable_keys = TableColumn::nullable_keys(&primary_keys, columns);
+            has_nullable_keys = !nullable_keys.is_empty();
+            if has_nullable_keys {
+                warn!(
+                    "Ordering of table {} is not deterministic, since its key columns {} may be NULL. \
+                     Rows with NULL keys may be reported as a data difference",
+                    table_name,
+                    nullable_keys.join(", ")
+                );
+            }
+        }
+
+        // Keys sorting differently in each database would split identical rows into different chunks
+        let mismatching_collations =
+            Self::mismatching_collations(&primary_keys, &first_columns, &second_columns);
+        if !mismatching_collations.is_empty() {
+            warn!(
+                "Collations of the key columns {} of table {} differ between the databases. \
+                 Ordering them byte by byte (COLLATE \"C\") instead, which may not use their index",
+                mismatching_collations.join(", "),
+                table_name
+            );
+        }
+
+        let table_projection = Self::table_projection(
+            diff_payload,
+            table_name,
+            &primary_keys,
+            first_columns,
+            second_columns,
+        );
+
+        let schema_name = SchemaName::new(schema_name.to_owned());
+        let query_table_name = TableName::new(table_name.to_string());
+        let table_offset = TableOffset::new(diff_payload.table_chunk_size(table_name));
+        // Prepare the primary keys for the table, unless an expression overrides them
+        // Will be used for query ordering when hashing data
+        let table_primary_keys = match diff_payload.order_by_expression(table_name) {
+            Some(expression) => {
+                let missing_keys = TablePrimaryKeys::missing_from(expression, &primary_keys);
+                if primary_keys.is_empty() {
+                    warn!(
+                        "Ordering of table {} by {} cannot be verified to be unique, since it has no key columns. \
+                         Rows ordered the same may be reported as a data difference",
+                        table_name, expression
+                    );
+                } else if !missing_keys.is_empty() {
+                    warn!(
+                        "Ordering of table {} by {} may not be unique, since it does not include its key columns {}. \
+                         Rows ordered the same may be reported as a data difference",
+                        table_name,
+                        expression,
+                        missing_keys.join(", ")
+                    );
+                }
+                TablePrimaryKeys::new(expression)
+            }
+            // NULL keys never compare as greater than a cursor, so their rows are paged by offset
+            None if has_nullable_keys => TablePrimaryKeys::from_columns(primary_keys)
+                .with_binary_collation(&mismatching_collations)
+                .without_keyset(),
+            None => TablePrimaryKeys::from_columns(primary_keys)
+                .with_binary_collation(&mismatching_collations),
+        };
+
+        let start = Instant::now();
+
+        let table_diff_result = match self
+            .diff_table_data(
+                diff_payload,
+                schema_name,
+                query_table_name,
+                second_table_name,
+                table_projection,
+                table_offset,
+                table_primary_keys,
+                first_filter.clone(),
+                second_filter.clone(),
+                table_sample.clone(),
+                total_rows,
+                table_counts_elapsed,
+                start,
+            )
+            .await
+        {
+            Some(table_diff_result) => table_diff_result,
+            None => TableDiffOutput::NoDiffWithDuration(
+                table_name.to_string(),
+                TableTimings::new(table_counts_elapsed, start.elapsed()),
+            ),
+        };
+
+        match table_sample.percent() {
+            Some(percent) => TableDiffOutput::Sampled(Box::new(table_diff_result), percent),
+            None => table_diff_result,
+        }
     }
 
-    pub async fn get_all_tables(&self, diff_payload: &DiffPayload) -> Result<Vec<String>> {
+    pub async fn get_all_tables(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: String,
+    ) -> Result<Vec<String>> {
         let input = QueryTableNamesInput::new(
-            SchemaName::new(diff_payload.schema_name().to_string()),
+            SchemaName::new(schema_name),
             diff_payload.included_tables().to_vec(),
             diff_payload.excluded_tables().to_vec(),
-        );
+        )
+        .with_table_types(diff_payload.include_table_types().to_vec());
 
         let tables = self
             .single_table_query_executor
@@ -197,6 +753,587 @@ impl<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor>
         Ok(tables)
     }
 
+    /// Returns the views and materialized views of the schema, if views are compared as well.
+    async fn get_all_views(&self, diff_payload: &DiffPayload, schema_name: String) -> Vec<String> {
+        if !diff_payload.include_views() {
+            return Vec::new();
+        }
+
+        let input = QueryTableNamesInput::new(
+            SchemaName::new(schema_name),
+            diff_payload.included_tables().to_vec(),
+            diff_payload.excluded_tables().to_vec(),
+        );
+
+        self.single_table_query_executor
+            .query_view_names(input)
+            .await
+    }
+
+    /// Returns the primary key columns of the table, falling back to the columns of a unique index, if allowed.
+    /// The primary keys of all the tables are fetched at once, the first time the keys of a table are needed.
+    ///
+    /// Keys that rows cannot be ordered by (e.g. holding an empty column name) are discarded,
+    /// so that the table is reported without a key rather than failing its hash queries.
+    async fn table_keys(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: &str,
+        table_name: &str,
+        primary_key_cache: &PrimaryKeyCache,
+    ) -> Vec<String> {
+        let keys = self
+            .queried_table_keys(diff_payload, schema_name, table_name, primary_key_cache)
+            .await;
+
+        if !keys.is_empty() && !TablePrimaryKeys::is_well_formed(&keys) {
+            warn!(
+                "Ignoring the malformed key columns {:?} of {}",
+                keys, table_name
+            );
+            return Vec::new();
+        }
+
+        keys
+    }
+
+    /// Queries the key columns of the table, as described by [`TableDiffer::table_keys`].
+    async fn queried_table_keys(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: &str,
+        table_name: &str,
+        primary_key_cache: &PrimaryKeyCache,
+    ) -> Vec<String> {
+        let primary_keys = primary_key_cache
+            .primary_keys
+            .get_or_init(|| async {
+                let input = QueryAllPrimaryKeysInput::new(
+                    SchemaName::new(primary_key_cache.schema_name.to_owned()),
+                    primary_key_cache.table_names.to_vec(),
+                );
+                self.single_table_query_executor
+                    .query_all_primary_keys(input)
+                    .await
+                    .into_iter()
+                    .map(|(table_name, primary_keys)| {
+                        let key = (primary_key_cache.schema_name.to_owned(), table_name);
+                        (key, primary_keys)
+                    })
+                    .collect()
+            })
+            .await
+            .get(&(schema_name.to_owned(), table_name.to_owned()))
+            .cloned()
+            .unwrap_or_default();
+
+        if !primary_keys.is_empty() || !diff_payload.allow_unique_key_fallback() {
+            return primary_keys;
+        }
+
+        let query_unique_keys_input = QueryPrimaryKeysInput::new(
+            SchemaName::new(schema_name.to_owned()),
+            table_name.to_string(),
+        );
+
+        match self
+            .single_table_query_executor
+            .query_unique_keys(query_unique_keys_input)
+            .await
+        {
+            Ok(unique_keys) => unique_keys,
+            Err(e) => {
+                warn!("Cannot fall back to a unique key of {}: {}", table_name, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Counts the rows of the table in both databases.
+    ///
+    /// In count-only runs, the counts are read from a single `UNION ALL` query per database,
+    /// batched over all the tables, saving a round trip per table. The counts are still exact,
+    /// unlike `estimate_counts` which reads the statistics, but a single table failing to be
+    /// counted (e.g. dropped since being listed) fails the batch of its database, and a statement
+    /// timeout bounds the whole batch rather than each count. The tables of a failed batch are
+    /// then counted one at a time, so that the error is reported for the right table.
+    async fn table_counts(
+        &self,
+        table_name: &str,
+        query_count_input: QueryTableCountInput,
+        first_filter: &TableFilter,
+        second_filter: &TableFilter,
+        table_count_cache: &TableCountCache,
+    ) -> (Result<i64>, Result<i64>) {
+        if !table_count_cache.table_names.is_empty() {
+            let (first_counts, second_counts) = table_count_cache
+                .counts
+                .get_or_init(|| async {
+                    let input = QueryTableCountsInput::new(
+                        SchemaName::new(table_count_cache.schema_name.to_owned()),
+                        table_count_cache.table_names.to_vec(),
+                        first_filter.clone(),
+                        second_filter.clone(),
+                    );
+                    let (first_counts, second_counts) = self
+                        .dual_table_query_executor
+                        .query_table_counts(input)
+                        .await;
+
+                    let batch_counts = |counts: Result<HashMap<String, i64>>| {
+                        counts
+                            .inspect_err(|e| {
+                                warn!("Counting the tables one at a time, since the batched count failed: {}", e)
+                            })
+                            .ok()
+                    };
+                    (batch_counts(first_counts), batch_counts(second_counts))
+                })
+                .await;
+
+            let count_of = |counts: &Option<HashMap<String, i64>>| {
+                counts
+                    .as_ref()
+                    .and_then(|counts| counts.get(table_name).copied())
+            };
+            if let (Some(first_count), Some(second_count)) =
+                (count_of(first_counts), count_of(second_counts))
+            {
+                return (Ok(first_count), Ok(second_count));
+            }
+        }
+
+        self.dual_table_query_executor
+            .query_table_count(query_count_input)
+            .await
+    }
+
+    /// Samples up to `samples` hash windows, evenly spread over the rows both tables have in common,
+    /// to tell whether their counts differ because rows were only appended or because they diverged.
+    ///
+    /// Returns `None` when the table has no key to order its rows by.
+    #[allow(clippy::too_many_arguments)]
+    async fn count_drift(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: &str,
+        table_name: &str,
+        count_diff: &TableCountDiff,
+        samples: u64,
+        first_filter: &TableFilter,
+        second_filter: &TableFilter,
+        primary_key_cache: &PrimaryKeyCache,
+    ) -> Option<TableDrift> {
+        let primary_keys = self
+            .table_keys(diff_payload, schema_name, table_name, primary_key_cache)
+            .await;
+
+        if primary_keys.is_empty() {
+            warn!(
+                "Cannot sample the rows of {} without a primary key",
+                table_name
+            );
+            return None;
+        }
+
+        let second_table_name = TableName::new(diff_payload.second_table_name(table_name));
+
+        let query_table_columns_input = QueryTableColumnsInput::new(
+            SchemaName::new(schema_name.to_owned()),
+            TableName::new(table_name.to_owned()),
+        )
+        .with_second_table_name(second_table_name.clone());
+
+        let (first_columns, second_columns) = self
+            .dual_table_query_executor
+            .query_table_columns(query_table_columns_input)
+            .await;
+
+        let mismatching_collations =
+            Self::mismatching_collations(&primary_keys, &first_columns, &second_columns);
+
+        let table_projection = Self::table_projection(
+            diff_payload,
+            table_name,
+            &primary_keys,
+            first_columns,
+            second_columns,
+        );
+
+        let table_hash_expression = diff_payload
+            .hash_expression()
+            .map(TableHashExpression::new)
+            .unwrap_or_default();
+
+        let table_primary_keys = TablePrimaryKeys::from_columns(primary_keys)
+            .with_binary_collation(&mismatching_collations);
+
+        let common_rows = count_diff.first().min(count_diff.second());
+        let chunk_size = diff_payload.table_chunk_size(table_name);
+
+        let mut mismatching_windows = Vec::new();
+        for position in Self::sample_positions(common_rows, chunk_size, samples) {
+            // The last window must not reach past the rows both tables have in common
+            let window_size = chunk_size.min(common_rows - position);
+
+            let input = QueryHashDataInput::new(
+                SchemaName::new(schema_name.to_owned()),
+                TableName::new(table_name.to_owned()),
+                table_projection.clone(),
+                table_hash_expression.clone(),
+                table_primary_keys.clone(),
+                TablePosition::new(position),
+                TableOffset::new(window_size),
+                first_filter.clone(),
+                second_filter.clone(),
+            )
+            .with_second_table_name(second_table_name.clone());
+
+            let (first_hash, second_hash) =
+                match self.dual_table_query_executor.query_hash_data(input).await {
+                    (Ok(first_hash), Ok(second_hash)) => (first_hash, second_hash),
+                    (Err(e), _) | (_, Err(e)) => {
+                        warn!("Cannot sample the rows of {}: {}", table_name, e);
+                        return None;
+                    }
+                };
+
+            if first_hash != second_hash {
+                mismatching_windows.push((position, position + window_size));
+            }
+        }
+
+        if mismatching_windows.is_empty() {
+            Some(TableDrift::AppendOnly)
+        } else {
+            Some(TableDrift::Scattered(mismatching_windows))
+        }
+    }
+
+    /// Returns the start of up to `samples` windows of `chunk_size` rows, evenly spread over
+    /// the given rows from the first to the last window. A single sample covers the last window.
+    fn sample_positions(rows: i64, chunk_size: i64, samples: u64) -> Vec<i64> {
+        let windows = (rows + chunk_size - 1) / chunk_size;
+        let samples = (samples as i64).min(windows);
+
+        (0..samples)
+            .map(|sample| match samples {
+                1 => windows - 1,
+                _ => sample * (windows - 1) / (samples - 1),
+            })
+            .map(|window| window * chunk_size)
+            .collect()
+    }
+
+    /// Streams the windows of rows to hash, from the requested start position (e.g. to resume an
+    /// interrupted run), along with the cursor after which each window starts, if any.
+    ///
+    /// Rows ordered by their keys are paged by keys: the cursor of each window is read from the
+    /// first database by walking the index of the keys from the previous cursor, so that no window
+    /// skips the rows of the previous ones with an ever deeper `OFFSET`. Other rows are paged by offset.
+    /// A cursor that cannot be read is yielded as the error of its window, which ends the stream.
+    #[allow(clippy::too_many_arguments)]
+    fn hash_windows<'a>(
+        &'a self,
+        diff_payload: &'a DiffPayload,
+        schema_name: &'a SchemaName,
+        table_name: &'a TableName,
+        primary_keys: &'a TablePrimaryKeys,
+        filter: &'a TableFilter,
+        sample: &'a TableSample,
+        total_rows: i64,
+    ) -> impl Stream<Item = (i64, Result<Option<TableKeyCursor>>)> + 'a {
+        let chunk_size = diff_payload.table_chunk_size(table_name.name());
+        let start_position = diff_payload.start_position();
+
+        if !primary_keys.is_keyset() {
+            let positions = (start_position..total_rows).step_by(chunk_size as usize);
+            return futures::stream::iter(positions.map(|position| (position, Ok(None))))
+                .left_stream();
+        }
+
+        let key_cursor = move |cursor: Option<TableKeyCursor>, offset: i64| {
+            let filter = match &cursor {
+                Some(cursor) => filter.and(primary_keys.after(cursor)),
+                None => filter.clone(),
+            };
+            let input = QueryKeyCursorInput::new(
+                schema_name.clone(),
+                table_name.clone(),
+                primary_keys.clone(),
+                TableOffset::new(offset),
+                filter,
+            )
+            .with_sample(sample.clone());
+
+            self.dual_table_query_executor.query_key_cursor(input)
+        };
+
+        let first_window = (start_position < total_rows).then_some((start_position, Ok(None)));
+
+        futures::stream::unfold(first_window, move |window| async move {
+            let (position, cursor) = window?;
+            let cursor = match cursor {
+                // The rows before the start position are skipped once, through the keys of the last of them
+                Ok(None) if position > 0 => match key_cursor(None, position - 1).await {
+                    Ok(Some(cursor)) => Some(cursor),
+                    Ok(None) => return None,
+                    Err(e) => return Some(((position, Err(e)), None)),
+                },
+                Ok(cursor) => cursor,
+                Err(e) => return Some(((position, Err(e)), None)),
+            };
+
+            let next_position = position + chunk_size;
+            let next_window = if next_position < total_rows {
+                // The next window starts after the last row of this one
+                match key_cursor(cursor.clone(), chunk_size - 1).await {
+                    Ok(None) => None,
+                    next_cursor => Some((next_position, next_cursor)),
+                }
+            } else {
+                None
+            };
+
+            Some(((position, Ok(cursor)), next_window))
+        })
+        .right_stream()
+    }
+
+    /// Builds the sample of the pages hashed in both databases, with the same seed on each.
+    fn table_sample(diff_payload: &DiffPayload) -> TableSample {
+        diff_payload
+            .sample_percent()
+            .map(|percent| TableSample::new(percent, diff_payload.sample_seed()))
+            .unwrap_or_default()
+    }
+
+    /// Builds the filters applied to the first and the second database respectively.
+    ///
+    /// The row filter is applied identically to both sides, so that hashes remain comparable.
+    /// When a temporal column is configured, each side is additionally restricted to its own snapshot.
+    fn table_filters(diff_payload: &DiffPayload) -> (TableFilter, TableFilter) {
+        let side_filter = |as_of: Option<&str>| {
+            let mut conditions = Vec::new();
+
+            if let Some(row_filter) = diff_payload.row_filter() {
+                conditions.push(row_filter.to_string());
+            }
+
+            if let (Some(column), Some(as_of)) = (diff_payload.temporal_column(), as_of) {
+                conditions.push(
+                    TableTemporalPredicate::new(column, as_of)
+                        .with_end_column(diff_payload.temporal_end_column())
+                        .condition(),
+                );
+            }
+
+            if let (Some(column), Some(since)) = (
+                diff_payload.changed_since_column(),
+                diff_payload.changed_since(),
+            ) {
+                conditions.push(TableChangedSincePredicate::new(column, since).condition());
+            }
+
+            TableFilter::new(conditions)
+        };
+
+        (
+            side_filter(diff_payload.first_db_as_of()),
+            side_filter(diff_payload.second_db_as_of()),
+        )
+    }
+
+    /// Returns the key columns whose collation differs between both databases, if their columns are known.
+    fn mismatching_collations(
+        primary_keys: &[String],
+        first_columns: &Result<Vec<TableColumn>>,
+        second_columns: &Result<Vec<TableColumn>>,
+    ) -> Vec<String> {
+        match (first_columns, second_columns) {
+            (Ok(first_columns), Ok(second_columns)) => {
+                TableColumn::collation_mismatches(primary_keys, first_columns, second_columns)
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Checks that the tracking column of the incremental mode exists in the table of both databases,
+    /// since otherwise every query of the table would fail on it.
+    async fn changed_since_column_error(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: &str,
+        table_name: &str,
+        second_table_name: &TableName,
+    ) -> Option<TableDiffOutput> {
+        let column = diff_payload.changed_since_column()?;
+
+        let query_table_columns_input = QueryTableColumnsInput::new(
+            SchemaName::new(schema_name.to_owned()),
+            TableName::new(table_name.to_owned()),
+        )
+        .with_second_table_name(second_table_name.clone());
+
+        let (first_columns, second_columns) = self
+            .dual_table_query_executor
+            .query_table_columns(query_table_columns_input)
+            .await;
+
+        // Failures to list the columns are left to the queries of the table to report
+        let has_column = |columns: &[TableColumn]| columns.iter().any(|c| c.name() == column);
+        let missing_in = match (first_columns, second_columns) {
+            (Ok(first_columns), _) if !has_column(&first_columns) => "first",
+            (_, Ok(second_columns)) if !has_column(&second_columns) => "second",
+            _ => return None,
+        };
+
+        Some(TableDiffOutput::Error(
+            table_name.to_owned(),
+            format!(
+                "Tracking column {} does not exist in the {} database",
+                column, missing_in
+            ),
+        ))
+    }
+
+    /// Builds the filters restricting both databases to the middle `percentage` percent of the
+    /// key range, computed over the keys of both databases so that the same rows are compared.
+    ///
+    /// Returns `None` when the range cannot be computed (e.g. for a composite or non-integer key),
+    /// in which case the whole table is compared.
+    #[allow(clippy::too_many_arguments)]
+    async fn key_range_filters(
+        &self,
+        schema_name: &str,
+        table_name: &str,
+        second_table_name: &TableName,
+        primary_keys: &[String],
+        percentage: u64,
+        first_filter: &TableFilter,
+        second_filter: &TableFilter,
+    ) -> Option<(TableFilter, TableFilter)> {
+        let [key] = primary_keys else {
+            warn!(
+                "Comparing the whole of table {}, since key ranges require a single key column",
+                table_name
+            );
+            return None;
+        };
+
+        let key = TablePrimaryKeys::from_columns(vec![key.to_owned()]);
+
+        let query_key_range_input = QueryKeyRangeInput::new(
+            SchemaName::new(schema_name),
+            TableName::new(table_name),
+            key.clone(),
+            first_filter.clone(),
+            second_filter.clone(),
+        )
+        .with_second_table_name(second_table_name.clone());
+
+        match self
+            .dual_table_query_executor
+            .query_key_range(query_key_range_input)
+            .await
+        {
+            (Ok(first_range), Ok(second_range)) => {
+                let condition = first_range
+                    .union(&second_range)
+                    .middle(percentage)
+                    .condition(key.keys());
+
+                Some((
+                    first_filter.and(condition.as_str()),
+                    second_filter.and(condition),
+                ))
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                warn!(
+                    "Comparing the whole of table {}, since its key range is unavailable: {}",
+                    table_name, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Builds the projection used for hashing from the columns of the first database,
+    /// falling back to the second one. Selects all columns when neither is available.
+    ///
+    /// Columns matching any of the excluded column patterns, as well as the columns excluded
+    /// from every table or from this table, are left out of the projection.
+    fn table_projection(
+        diff_payload: &DiffPayload,
+        table_name: &str,
+        primary_keys: &[String],
+        first_columns: Result<Vec<TableColumn>>,
+        second_columns: Result<Vec<TableColumn>>,
+    ) -> TableProjection {
+        let columns = match (first_columns, second_columns) {
+            (Ok(columns), _) if !columns.is_empty() => columns,
+            (_, Ok(columns)) => columns,
+            _ => Vec::new(),
+        };
+
+        let excluded_patterns = diff_payload
+            .exclude_column_patterns()
+            .iter()
+            .map(ColumnPattern::new)
+            .collect::<Vec<ColumnPattern>>();
+
+        let hash_columns = diff_payload.table_hash_columns(table_name);
+        if let Some(hash_columns) = hash_columns {
+            let missing_columns = hash_columns
+                .iter()
+                .filter(|hash_column| !columns.iter().any(|c| c.name() == hash_column.as_str()))
+                .map(String::as_str)
+                .collect::<Vec<&str>>();
+            if !columns.is_empty() && !missing_columns.is_empty() {
+                warn!(
+                    "Hash columns {} do not exist in table {}, and are left out of its data hashes",
+                    missing_columns.join(", "),
+                    table_name
+                );
+            }
+        }
+
+        // Hash exactly the requested columns, along with the keys identifying their rows, if restricted
+        let table_projection = TableProjection::new(
+            columns
+                .iter()
+                .map(|column| column.name())
+                .filter(|name| match hash_columns {
+                    Some(hash_columns) => {
+                        hash_columns.iter().any(|c| c == name)
+                            || primary_keys.iter().any(|key| key == name)
+                    }
+                    None => {
+                        !excluded_patterns.iter().any(|p| p.matches(name))
+                            && !diff_payload.is_column_excluded(table_name, name)
+                    }
+                })
+                .map(|name| name.to_string())
+                .collect::<Vec<String>>(),
+        );
+
+        // Hash NULL and empty text identically, if requested
+        if !diff_payload.coalesce_nulls() {
+            return table_projection;
+        }
+
+        table_projection.with_coalesced_columns(
+            columns
+                .iter()
+                .filter(|column| column.is_text())
+                .map(|column| column.name())
+                .collect::<Vec<&str>>(),
+        )
+    }
+
     fn extract_result(
         table_name: &str,
         first_result: Result<i64>,
@@ -204,7 +1341,19 @@ impl<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor>
     ) -> TableDiffOutput {
         match (first_result, second_result) {
             (Ok(first_total_rows), Ok(second_total_rows)) => {
-                if first_total_rows != second_total_rows {
+                if first_total_rows == 0 && second_total_rows != 0 {
+                    TableDiffOutput::EmptyOnOneSide(
+                        table_name.to_owned(),
+                        TableSource::First(None),
+                        second_total_rows,
+                    )
+                } else if second_total_rows == 0 && first_total_rows != 0 {
+                    TableDiffOutput::EmptyOnOneSide(
+                        table_name.to_owned(),
+                        TableSource::Second(None),
+                        first_total_rows,
+                    )
+                } else if first_total_rows != second_total_rows {
                     TableDiffOutput::Diff(
                         table_name.to_owned(),
                         TableCountDiff::new(first_total_rows, second_total_rows),
@@ -213,8 +1362,21 @@ impl<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor>
                     TableDiffOutput::NoCountDiff(table_name.to_owned(), first_total_rows)
                 }
             }
-            (Err(_e), _) => TableDiffOutput::NotExists(table_name.to_owned(), TableSource::First),
-            (_, Err(_e)) => TableDiffOutput::NotExists(table_name.to_owned(), TableSource::Second),
+            (Err(e), _) if QueryTimeoutError::is_timeout(&e) => {
+                TableDiffOutput::Timeout(table_name.to_owned(), TableSource::First(None))
+            }
+            (_, Err(e)) if QueryTimeoutError::is_timeout(&e) => {
+                TableDiffOutput::Timeout(table_name.to_owned(), TableSource::Second(None))
+            }
+            (Err(e), _) if RelationNotFoundError::is_not_found(&e) => {
+                TableDiffOutput::NotExists(table_name.to_owned(), TableSource::First(None))
+            }
+            (_, Err(e)) if RelationNotFoundError::is_not_found(&e) => {
+                TableDiffOutput::NotExists(table_name.to_owned(), TableSource::Second(None))
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                TableDiffOutput::Error(table_name.to_owned(), e.to_string())
+            }
         }
     }
     #[allow(clippy::too_many_arguments)]
@@ -223,47 +1385,154 @@ impl<TQE: TableSingleSourceQueryExecutor, DTQE: TableDualSourceQueryExecutor>
         diff_payload: &DiffPayload,
         schema_name: SchemaName,
         query_table_name: TableName,
+        second_table_name: TableName,
+        table_projection: TableProjection,
         table_offset: TableOffset,
         table_primary_keys: TablePrimaryKeys,
+        first_filter: TableFilter,
+        second_filter: TableFilter,
+        table_sample: TableSample,
         total_rows: i64,
+        table_counts_elapsed: Duration,
         start: Instant,
     ) -> Option<TableDiffOutput> {
-        // Start data comparison
-        let mut position = diff_payload.start_position();
-        while position <= total_rows {
-            let input = QueryHashDataInput::new(
-                schema_name.clone(),
+        // Stream all rows through COPY and compare them client-side, if requested
+        if diff_payload.copy_stream() {
+            let input = QueryCopyRowsInput::new(
+                schema_name,
                 query_table_name.clone(),
-                table_primary_keys.clone(),
-                TablePosition::new(position),
-                table_offset.clone(),
-            );
+                table_projection,
+                table_primary_keys,
+                first_filter,
+                second_filter,
+            )
+            .with_second_table_name(second_table_name);
 
-            let hash_fetch_start = Instant::now();
-            let (first_hash, second_hash) =
-                self.dual_table_query_executor.query_hash_data(input).await;
-            let hash_fetch_elapsed = hash_fetch_start.elapsed();
-            debug!(
-                "Hashes for {} loaded in: {}ms",
-                query_table_name.name(),
-                hash_fetch_elapsed.as_millis()
-            );
+            let table_name = query_table_name.name().to_string();
+            return match self.dual_table_query_executor.query_copy_diff(input).await {
+                Ok(None) => None,
+                Ok(Some(position)) => Some(TableDiffOutput::DataDiffWithDuration(
+                    table_name,
+                    position,
+                    position + 1,
+                    TableTimings::new(table_counts_elapsed, start.elapsed()),
+                )),
+                Err(e) => Some(TableDiffOutput::Error(table_name, e.to_string())),
+            };
+        }
+
+        let table_spinner =
+            progress::table_spinner(diff_payload.progress(), query_table_name.name());
+        let chunk_size = diff_payload.table_chunk_size(query_table_name.name());
+
+        let table_hash_expression = diff_payload
+            .hash_expression()
+            .map(TableHashExpression::new)
+            .unwrap_or_default();
+
+        let windows = self.hash_windows(
+            diff_payload,
+            &schema_name,
+            &query_table_name,
+            &table_primary_keys,
+            &first_filter,
+            &table_sample,
+            total_rows,
+        );
+
+        // Hash up to `hash_parallelism` windows at once (one at a time by default). Hashes are
+        // yielded in the order of the windows, so the first mismatch is always the lowest differing
+        // window, and the windows still in flight are cancelled once it is found.
+        let hash_parallelism = diff_payload.hash_parallelism().unwrap_or(1) as usize;
+        let table_name = query_table_name.name();
+        let mut window_hashes = std::pin::pin!(windows
+            .map(|(position, cursor)| {
+                let input = cursor.map(|cursor| {
+                    // A window after a cursor starts at its first row, rather than at its position
+                    let (window_position, first_filter, second_filter) = match cursor {
+                        Some(cursor) => (
+                            0,
+                            first_filter.and(table_primary_keys.after(&cursor)),
+                            second_filter.and(table_primary_keys.after(&cursor)),
+                        ),
+                        None => (position, first_filter.clone(), second_filter.clone()),
+                    };
+
+                    QueryHashDataInput::new(
+                        schema_name.clone(),
+                        query_table_name.clone(),
+                        table_projection.clone(),
+                        table_hash_expression.clone(),
+                        table_primary_keys.clone(),
+                        TablePosition::new(window_position),
+                        table_offset.clone(),
+                        first_filter,
+                        second_filter,
+                    )
+                    .with_second_table_name(second_table_name.clone())
+                    .with_sample(table_sample.clone())
+                });
+
+                let window_span = debug_span!("hash_window", position);
+
+                async move {
+                    let hash_fetch_start = Instant::now();
+                    let hashes = match input {
+                        Ok(input) => self.dual_table_query_executor.query_hash_data(input).await,
+                        Err(e) => (Err(e), Ok(String::new())),
+                    };
+                    let hash_fetch_elapsed = hash_fetch_start.elapsed();
+                    debug!(
+                        "Hashes for {} loaded in: {}ms",
+                        table_name,
+                        hash_fetch_elapsed.as_millis()
+                    );
+
+                    (position, hashes)
+                }
+                .instrument(window_span)
+            })
+            .buffered(hash_parallelism));
+
+        while let Some((position, (first_hash, second_hash))) = window_hashes.next().await {
+            table_spinner.set_message(format!(
+                "{}/{} rows",
+                (position + chunk_size).min(total_rows),
+                total_rows
+            ));
+
+            // A failed query says nothing about the data, so report it rather than a difference
+            let (first_hash, second_hash) = match (first_hash, second_hash) {
+                (Ok(first_hash), Ok(second_hash)) => (first_hash, second_hash),
+                (Err(e), _) | (_, Err(e)) => {
+                    table_spinner.finish_and_clear();
+                    return Some(TableDiffOutput::Error(
+                        query_table_name.name().to_string(),
+                        format!(
+                            "Hashing rows [{},{}] failed: {}",
+                            position,
+                            position + chunk_size,
+                            e
+                        ),
+                    ));
+                }
+            };
 
             // If hashes are different, return the result
             if first_hash != second_hash {
+                table_spinner.finish_and_clear();
                 let elapsed = start.elapsed();
                 return Some(TableDiffOutput::DataDiffWithDuration(
                     query_table_name.name().to_string(),
                     position,
-                    position + diff_payload.chunk_size(),
-                    elapsed,
+                    position + chunk_size,
+                    TableTimings::new(table_counts_elapsed, elapsed),
                 ));
             }
-
-            // Increase the position for the next iteration
-            position += diff_payload.chunk_size();
         }
 
+        table_spinner.finish_and_clear();
+
         None
     }
 }
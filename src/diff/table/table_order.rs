@@ -0,0 +1,82 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// The order in which the tables of a schema are compared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TableOrder {
+    /// Tables are compared by name, case insensitively.
+    #[default]
+    Name,
+    /// The smallest tables, by estimated row count, are compared first.
+    SizeAsc,
+    /// The largest tables, by estimated row count, are compared first.
+    SizeDesc,
+}
+
+impl TableOrder {
+    /// Returns whether the order needs the estimated row counts of the tables.
+    pub fn is_by_size(&self) -> bool {
+        matches!(self, Self::SizeAsc | Self::SizeDesc)
+    }
+
+    /// Sorts the tables, whose names are given by `name_of`, in this order.
+    /// Tables of the same size stay ordered by name, and tables without an estimate
+    /// (e.g. only found in the second database) count as empty.
+    pub fn sort<T>(
+        &self,
+        tables: &mut [T],
+        name_of: impl Fn(&T) -> &str,
+        row_estimates: &HashMap<String, i64>,
+    ) {
+        tables.sort_by_key(|table| name_of(table).to_lowercase());
+
+        let estimate_of = |table: &T| row_estimates.get(name_of(table)).copied().unwrap_or(0);
+        match self {
+            Self::Name => {}
+            Self::SizeAsc => tables.sort_by_key(estimate_of),
+            Self::SizeDesc => tables.sort_by_key(|table| Reverse(estimate_of(table))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(order: TableOrder) -> Vec<&'static str> {
+        let mut tables = vec!["orders", "Accounts", "events", "customers", "audit"];
+        let row_estimates = HashMap::from([
+            ("orders".to_string(), 5_000),
+            ("Accounts".to_string(), 20),
+            ("events".to_string(), 1_000_000),
+            ("customers".to_string(), 20),
+        ]);
+
+        order.sort(&mut tables, |table| *table, &row_estimates);
+        tables
+    }
+
+    #[test]
+    fn test_sort_by_name() {
+        assert_eq!(
+            sorted(TableOrder::Name),
+            vec!["Accounts", "audit", "customers", "events", "orders"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_size() {
+        assert_eq!(
+            sorted(TableOrder::SizeAsc),
+            vec!["audit", "Accounts", "customers", "orders", "events"]
+        );
+        assert_eq!(
+            sorted(TableOrder::SizeDesc),
+            vec!["events", "orders", "Accounts", "customers", "audit"]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::{anyhow, bail, Result};
+use clap::ValueEnum;
+use native_tls::{Certificate, Identity, TlsConnector};
+use postgres_native_tls::MakeTlsConnector;
+use serde::{Deserialize, Serialize};
+
+const PEM_CERTIFICATE_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+const PEM_CERTIFICATE_END: &str = "-----END CERTIFICATE-----";
+
+/// The `sslmode` used to connect to both databases, following the libpq modes of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SslMode {
+    /// Connections are not encrypted.
+    Disable,
+    /// Connections are encrypted, without verifying the server certificate unless a CA
+    /// certificate file is given, in which case it behaves like `verify-ca`.
+    Require,
+    /// Connections are encrypted and the server certificate must be signed by a trusted CA.
+    VerifyCa,
+    /// Like `verify-ca`, and the server host name must also match its certificate.
+    VerifyFull,
+}
+
+impl SslMode {
+    /// Returns whether the mode verifies the certificate of the server.
+    pub fn verifies_certificate(&self) -> bool {
+        matches!(self, Self::VerifyCa | Self::VerifyFull)
+    }
+
+    /// Maps the mode to the (coarser) mode enforced by the Postgres client.
+    pub(crate) fn pg_ssl_mode(&self) -> deadpool_postgres::SslMode {
+        match self {
+            Self::Disable => deadpool_postgres::SslMode::Disable,
+            Self::Require | Self::VerifyCa | Self::VerifyFull => {
+                deadpool_postgres::SslMode::Require
+            }
+        }
+    }
+}
+
+/// Builds the TLS connector of a database connection, if it is encrypted.
+///
+/// Without an SSL mode, only connections accepting invalid certificates or authenticating with
+/// a client certificate are encrypted, as before SSL modes were supported. Given a CA certificate
+/// file, only its certificates are trusted. Given a client certificate and key, as `(cert, key)`
+/// paths, the client authenticates with them (mTLS), regardless of how the server is verified.
+pub(crate) fn tls_connector(
+    ssl_mode: Option<SslMode>,
+    ca_cert_file: Option<&str>,
+    accept_invalid_certs: bool,
+    client_identity: Option<(&str, &str)>,
+) -> Result<Option<MakeTlsConnector>> {
+    let mut builder = TlsConnector::builder();
+
+    match ssl_mode {
+        None if accept_invalid_certs => {
+            builder.danger_accept_invalid_certs(true);
+        }
+        None if client_identity.is_some() => {}
+        None | Some(SslMode::Disable) => return Ok(None),
+        Some(SslMode::Require) if ca_cert_file.is_none() => {
+            builder.danger_accept_invalid_certs(true);
+        }
+        Some(SslMode::Require) | Some(SslMode::VerifyCa) => {
+            builder.danger_accept_invalid_hostnames(true);
+        }
+        Some(SslMode::VerifyFull) => {}
+    }
+
+    if let (Some(ca_cert_file), Some(_)) = (ca_cert_file, ssl_mode) {
+        builder.disable_built_in_roots(true);
+        for certificate in read_certificates(ca_cert_file)? {
+            builder.add_root_certificate(certificate);
+        }
+    }
+
+    if let Some((cert_file, key_file)) = client_identity {
+        builder.identity(read_identity(cert_file, key_file)?);
+    }
+
+    let tls_connector = builder
+        .build()
+        .map_err(|e| anyhow!("Failed to build the TLS connector: {}", e))?;
+
+    Ok(Some(MakeTlsConnector::new(tls_connector)))
+}
+
+/// Reads every certificate of a PEM file, e.g. a single CA certificate or a root bundle.
+fn read_certificates(ca_cert_file: &str) -> Result<Vec<Certificate>> {
+    let pem = std::fs::read_to_string(ca_cert_file)
+        .map_err(|e| anyhow!("Failed to read CA certificate file {}: {}", ca_cert_file, e))?;
+
+    let certificates = parse_certificates(&pem)
+        .map_err(|e| anyhow!("Invalid CA certificate file {}: {}", ca_cert_file, e))?;
+
+    if certificates.is_empty() {
+        bail!(
+            "No certificate found in CA certificate file {}",
+            ca_cert_file
+        );
+    }
+
+    Ok(certificates)
+}
+
+/// Reads the identity a client authenticates with.
+///
+/// A PKCS#12 archive (`.p12` or `.pfx`) bundles the certificate with its key, so the key file then
+/// holds the password of the archive instead. Otherwise, both files are in PEM format, the key in PKCS#8.
+fn read_identity(cert_file: &str, key_file: &str) -> Result<Identity> {
+    let read = |file: &str| {
+        std::fs::read(file).map_err(|e| anyhow!("Failed to read client file {}: {}", file, e))
+    };
+
+    let identity = if is_pkcs12(cert_file) {
+        let password = String::from_utf8(read(key_file)?)
+            .map_err(|_| anyhow!("Invalid password file {}", key_file))?;
+        Identity::from_pkcs12(&read(cert_file)?, password.trim_end_matches(['\r', '\n']))
+    } else {
+        Identity::from_pkcs8(&read(cert_file)?, &read(key_file)?)
+    };
+
+    identity.map_err(|e| anyhow!("Invalid client certificate {}: {}", cert_file, e))
+}
+
+fn is_pkcs12(cert_file: &str) -> bool {
+    std::path::Path::new(cert_file)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("p12") || extension.eq_ignore_ascii_case("pfx")
+        })
+}
+
+fn parse_certificates(pem: &str) -> Result<Vec<Certificate>> {
+    pem.split_inclusive(PEM_CERTIFICATE_END)
+        .filter(|block| block.contains(PEM_CERTIFICATE_BEGIN))
+        .map(|block| Certificate::from_pem(block.trim().as_bytes()).map_err(Into::into))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unencrypted_connections_have_no_tls_connector() {
+        assert!(tls_connector(None, None, false, None).unwrap().is_none());
+        assert!(tls_connector(Some(SslMode::Disable), None, true, None)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_encrypted_connections_have_a_tls_connector() {
+        assert!(tls_connector(None, None, true, None).unwrap().is_some());
+        assert!(tls_connector(Some(SslMode::Require), None, false, None)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_missing_ca_certificate_file() {
+        let error = tls_connector(Some(SslMode::VerifyFull), Some("missing.pem"), false, None)
+            .err()
+            .unwrap();
+
+        assert!(error
+            .to_string()
+            .starts_with("Failed to read CA certificate file missing.pem"));
+    }
+
+    #[test]
+    fn test_missing_client_certificate_file() {
+        let error = tls_connector(None, None, false, Some(("client.pem", "client.key")))
+            .err()
+            .unwrap();
+
+        assert!(error
+            .to_string()
+            .starts_with("Failed to read client file client.pem"));
+    }
+
+    #[test]
+    fn test_is_pkcs12() {
+        assert!(is_pkcs12("client.p12"));
+        assert!(is_pkcs12("client.PFX"));
+        assert!(!is_pkcs12("client.pem"));
+    }
+
+    #[test]
+    fn test_parse_certificates_without_certificate() {
+        assert!(parse_certificates("not a certificate").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pg_ssl_mode() {
+        assert!(matches!(
+            SslMode::Disable.pg_ssl_mode(),
+            deadpool_postgres::SslMode::Disable
+        ));
+        assert!(matches!(
+            SslMode::VerifyCa.pg_ssl_mode(),
+            deadpool_postgres::SslMode::Require
+        ));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod output;
+pub mod query;
+pub mod trigger_differ;
+
+#[cfg(test)]
+mod trigger_differ_tests;
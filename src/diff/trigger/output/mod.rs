@@ -0,0 +1,90 @@
+use colored::{ColoredString, Colorize};
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::table::query::output::TableSource;
+use crate::diff::types::{DiffOutputMarker, SchemaName};
+
+/// Represents the output of a trigger difference.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone)]
+pub enum TriggerDiffOutput {
+    /// Indicates that the trigger of the table is defined identically in both databases.
+    NoDiff(String, String),
+    /// Indicates that the trigger of the table does not exist in a specific source.
+    NotExists(String, String, TableSource),
+    /// Indicates that the trigger of the table is defined differently, along with both definitions.
+    DefinitionChanged(String, String, String, String),
+}
+
+impl TriggerDiffOutput {
+    /// Returns the name of the table this output refers to.
+    pub fn table_name(&self) -> &str {
+        match self {
+            Self::NoDiff(table, _)
+            | Self::NotExists(table, _, _)
+            | Self::DefinitionChanged(table, _, _, _) => table,
+        }
+    }
+
+    /// Returns the name of the trigger this output refers to.
+    pub fn trigger_name(&self) -> &str {
+        match self {
+            Self::NoDiff(_, trigger)
+            | Self::NotExists(_, trigger, _)
+            | Self::DefinitionChanged(_, trigger, _, _) => trigger,
+        }
+    }
+
+    /// Determines whether the trigger differs between the databases, including when it is missing
+    /// in one of them.
+    pub fn is_difference(&self) -> bool {
+        !matches!(self, Self::NoDiff(_, _))
+    }
+
+    /// Labels the source of the output, if any, with the alias given to its database.
+    pub fn with_source_aliases(
+        self,
+        first_alias: Option<&str>,
+        second_alias: Option<&str>,
+    ) -> Self {
+        match self {
+            Self::NotExists(table, trigger, source) => {
+                Self::NotExists(table, trigger, source.with_alias(first_alias, second_alias))
+            }
+            output => output,
+        }
+    }
+
+    /// Returns the plain, uncolored message of the output (e.g. for machine-readable reports).
+    pub fn message(&self) -> String {
+        match self {
+            Self::NoDiff(table, trigger) => {
+                format!("{}.{} - No difference in trigger", table, trigger)
+            }
+            Self::NotExists(table, trigger, source) => format!(
+                "{}.{} - Trigger does not exist in {}",
+                table, trigger, source
+            ),
+            Self::DefinitionChanged(table, trigger, first, second) => format!(
+                "{}.{} - Trigger definition differs (first: {}, second: {})",
+                table, trigger, first, second
+            ),
+        }
+    }
+
+    /// Converts the trigger difference output to a colored string.
+    pub fn to_string(&self) -> ColoredString {
+        let message = self.message();
+        match self {
+            Self::NoDiff(_, _) => message.green().bold(),
+            Self::NotExists(_, _, _) => message.red().bold().underline(),
+            Self::DefinitionChanged(_, _, _, _) => message.red().bold(),
+        }
+    }
+}
+
+impl DiffOutputMarker for TriggerDiffOutput {
+    fn convert(self, schema_name: SchemaName) -> DiffOutput {
+        DiffOutput::TriggerDiff(schema_name, self)
+    }
+}
@@ -0,0 +1,45 @@
+use crate::diff::types::SchemaName;
+
+/// Represents the input for querying the triggers of the tables of a schema.
+pub struct QueryTriggersInput {
+    schema_name: SchemaName,
+    included_tables: Vec<String>,
+    excluded_tables: Vec<String>,
+}
+
+impl QueryTriggersInput {
+    /// Creates a new `QueryTriggersInput` with the given schema name and tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_name` - The name of the schema to query.
+    /// * `included_tables` - The tables whose triggers are queried, all of them if empty.
+    /// * `excluded_tables` - The tables whose triggers are not queried.
+    ///
+    /// # Returns
+    ///
+    /// A new `QueryTriggersInput` instance.
+    pub fn new(
+        schema_name: SchemaName,
+        included_tables: Vec<impl Into<String>>,
+        excluded_tables: Vec<impl Into<String>>,
+    ) -> Self {
+        Self {
+            schema_name,
+            included_tables: included_tables.into_iter().map(|t| t.into()).collect(),
+            excluded_tables: excluded_tables.into_iter().map(|t| t.into()).collect(),
+        }
+    }
+
+    pub fn schema_name(&self) -> &SchemaName {
+        &self.schema_name
+    }
+
+    pub fn included_tables(&self) -> Vec<String> {
+        self.included_tables.to_vec()
+    }
+
+    pub fn excluded_tables(&self) -> Vec<String> {
+        self.excluded_tables.to_vec()
+    }
+}
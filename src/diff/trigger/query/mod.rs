@@ -0,0 +1,4 @@
+pub mod input;
+pub mod trigger_query;
+pub mod trigger_query_executor;
+pub mod trigger_types;
@@ -0,0 +1,64 @@
+use crate::diff::table::query::table_types::IncludedExcludedTables;
+use crate::diff::types::SchemaName;
+use std::fmt::{Display, Formatter};
+
+/// Represents a query for retrieving information about triggers.
+pub enum TriggerQuery {
+    /// Retrieves the triggers of the tables of a schema, along with their definitions.
+    AllTriggersForSchema(SchemaName, IncludedExcludedTables),
+}
+
+impl Display for TriggerQuery {
+    /// Formats the `TriggerQuery` as a string.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // Internal triggers (e.g. the ones enforcing foreign keys) are part of the constraints
+            Self::AllTriggersForSchema(schema_name, included_excluded_tables) => write!(
+                f,
+                r#"
+                SELECT table_name, trigger_name, definition
+                FROM (
+                    SELECT c.relname::text AS table_name, t.tgname::text AS trigger_name,
+                           pg_get_triggerdef(t.oid) AS definition
+                    FROM pg_trigger t
+                    JOIN pg_class c ON c.oid = t.tgrelid
+                    JOIN pg_namespace n ON n.oid = c.relnamespace
+                    WHERE n.nspname = '{}'
+                    AND NOT t.tgisinternal
+                ) AS triggers
+                WHERE true
+                {}
+                ORDER BY table_name, trigger_name;
+                "#,
+                schema_name.name(),
+                included_excluded_tables.statement()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::internal::tests::sanitize_raw_string;
+
+    impl From<TriggerQuery> for String {
+        fn from(value: TriggerQuery) -> Self {
+            value.to_string()
+        }
+    }
+
+    #[test]
+    fn test_all_triggers_for_schema_query() {
+        let schema_name = SchemaName::new("test_schema");
+        let included_excluded_tables =
+            IncludedExcludedTables::new(vec!["orders"], Vec::<String>::new());
+        let all_triggers_query =
+            TriggerQuery::AllTriggersForSchema(schema_name, included_excluded_tables);
+
+        assert_eq!(
+            sanitize_raw_string(all_triggers_query),
+            "SELECT table_name, trigger_name, definition FROM ( SELECT c.relname::text AS table_name, t.tgname::text AS trigger_name, pg_get_triggerdef(t.oid) AS definition FROM pg_trigger t JOIN pg_class c ON c.oid = t.tgrelid JOIN pg_namespace n ON n.oid = c.relnamespace WHERE n.nspname = 'test_schema' AND NOT t.tgisinternal ) AS triggers WHERE true AND table_name IN ('orders') ORDER BY table_name, trigger_name;"
+        );
+    }
+}
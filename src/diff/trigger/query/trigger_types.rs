@@ -0,0 +1,35 @@
+/// Represents a trigger of a table, along with its definition.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone)]
+pub struct TableTrigger {
+    table_name: String,
+    trigger_name: String,
+    definition: String,
+}
+
+impl TableTrigger {
+    pub fn new(
+        table_name: impl Into<String>,
+        trigger_name: impl Into<String>,
+        definition: impl Into<String>,
+    ) -> Self {
+        Self {
+            table_name: table_name.into(),
+            trigger_name: trigger_name.into(),
+            definition: definition.into(),
+        }
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    pub fn trigger_name(&self) -> &str {
+        &self.trigger_name
+    }
+
+    /// Returns the `CREATE TRIGGER` statement of the trigger, as reconstructed by the database.
+    pub fn definition(&self) -> &str {
+        &self.definition
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use anyhow::Result;
+use colored::Colorize;
+use tracing::{debug, info};
+
+use crate::diff::diff_output::DiffOutput;
+use crate::diff::diff_payload::DiffPayload;
+use crate::diff::table::query::output::TableSource;
+use crate::diff::trigger::output::TriggerDiffOutput;
+use crate::diff::trigger::query::input::QueryTriggersInput;
+use crate::diff::trigger::query::trigger_query_executor::TriggerSingleSourceQueryExecutor;
+use crate::diff::trigger::query::trigger_types::TableTrigger;
+use crate::diff::types::{DiffOutputMarker, SchemaName};
+
+pub struct TriggerDiffer<TQE: TriggerSingleSourceQueryExecutor> {
+    first_trigger_query_executor: TQE,
+    second_trigger_query_executor: TQE,
+}
+
+impl<TQE: TriggerSingleSourceQueryExecutor> TriggerDiffer<TQE> {
+    pub fn new(first_trigger_query_executor: TQE, second_trigger_query_executor: TQE) -> Self {
+        Self {
+            first_trigger_query_executor,
+            second_trigger_query_executor,
+        }
+    }
+
+    pub async fn diff_all_triggers(
+        &self,
+        diff_payload: &DiffPayload,
+        schema_name: String,
+    ) -> Result<Vec<DiffOutput>> {
+        info!("{}", "Starting trigger analysis…".yellow().bold());
+
+        let input = || {
+            QueryTriggersInput::new(
+                SchemaName::new(schema_name.to_owned()),
+                diff_payload.included_tables().to_vec(),
+                diff_payload.excluded_tables().to_vec(),
+            )
+        };
+
+        let start = Instant::now();
+        let (first_triggers, second_triggers) = futures::future::join(
+            self.first_trigger_query_executor.query_triggers(input()),
+            self.second_trigger_query_executor.query_triggers(input()),
+        )
+        .await;
+        debug!(
+            "Triggers for schema {} queried in: {}ms",
+            schema_name,
+            start.elapsed().as_millis()
+        );
+
+        let trigger_diff_results = Self::extract_result(first_triggers, second_triggers)
+            .into_iter()
+            .map(|output| {
+                output.with_source_aliases(diff_payload.first_alias(), diff_payload.second_alias())
+            })
+            .collect::<Vec<TriggerDiffOutput>>();
+
+        info!("##############################################");
+        info!("{}", "Trigger analysis results 👇".bright_magenta().bold());
+
+        for trigger_diff_result in trigger_diff_results
+            .iter()
+            .filter(|output| !diff_payload.only_differences() || output.is_difference())
+        {
+            info!("{}", trigger_diff_result.to_string());
+        }
+
+        info!("##############################################");
+
+        Ok(trigger_diff_results
+            .into_iter()
+            .map(|diff| diff.convert(SchemaName::new(schema_name.to_owned())))
+            .collect())
+    }
+
+    /// Pairs the triggers of both databases by table and trigger name, sorted by both.
+    fn extract_result(
+        first_triggers: Vec<TableTrigger>,
+        second_triggers: Vec<TableTrigger>,
+    ) -> Vec<TriggerDiffOutput> {
+        let mut paired: BTreeMap<(String, String), (Option<String>, Option<String>)> =
+            BTreeMap::new();
+
+        for trigger in first_triggers {
+            paired
+                .entry((
+                    trigger.table_name().to_owned(),
+                    trigger.trigger_name().to_owned(),
+                ))
+                .or_default()
+                .0 = Some(trigger.definition().to_owned());
+        }
+
+        for trigger in second_triggers {
+            paired
+                .entry((
+                    trigger.table_name().to_owned(),
+                    trigger.trigger_name().to_owned(),
+                ))
+                .or_default()
+                .1 = Some(trigger.definition().to_owned());
+        }
+
+        paired
+            .into_iter()
+            .filter_map(|((table, trigger), definitions)| match definitions {
+                (Some(first), Some(second)) if first == second => {
+                    Some(TriggerDiffOutput::NoDiff(table, trigger))
+                }
+                (Some(first), Some(second)) => Some(TriggerDiffOutput::DefinitionChanged(
+                    table, trigger, first, second,
+                )),
+                (Some(_), None) => Some(TriggerDiffOutput::NotExists(
+                    table,
+                    trigger,
+                    TableSource::Second(None),
+                )),
+                (None, Some(_)) => Some(TriggerDiffOutput::NotExists(
+                    table,
+                    trigger,
+                    TableSource::First(None),
+                )),
+                (None, None) => None,
+            })
+            .collect()
+    }
+}
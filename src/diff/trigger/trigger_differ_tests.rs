@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use crate::diff::diff_output::DiffOutput;
+    use crate::diff::diff_payload::DiffPayload;
+    use crate::diff::table::query::output::TableSource;
+    use crate::diff::trigger::output::TriggerDiffOutput;
+    use crate::diff::trigger::query::trigger_query_executor::MockTriggerSingleSourceQueryExecutor;
+    use crate::diff::trigger::query::trigger_types::TableTrigger;
+    use crate::diff::trigger::trigger_differ::TriggerDiffer;
+
+    const EMPTY_STRING_VEC: Vec<String> = Vec::new();
+
+    const AUDIT_TRIGGER: &str =
+        "CREATE TRIGGER audit AFTER INSERT ON public.table1 FOR EACH ROW EXECUTE FUNCTION audit()";
+
+    fn diff_payload() -> DiffPayload {
+        DiffPayload::builder()
+            .first_db("first_db")
+            .second_db("second_db")
+            .only_tables(false)
+            .only_sequences(false)
+            .only_count(false)
+            .chunk_size(10000)
+            .start_position(0)
+            .max_connections(10)
+            .include_tables(vec!["table1"])
+            .exclude_tables(EMPTY_STRING_VEC)
+            .schema_name("schema_name")
+            .accept_invalid_certs_first_db(false)
+            .accept_invalid_certs_second_db(false)
+            .include_triggers(true)
+            .build()
+    }
+
+    fn trigger_diffs(diff_output: Vec<DiffOutput>) -> Vec<TriggerDiffOutput> {
+        diff_output
+            .into_iter()
+            .map(|diff| match diff {
+                DiffOutput::TriggerDiff(_, trigger_diff) => trigger_diff,
+                _ => panic!("Expected DiffOutput::TriggerDiff"),
+            })
+            .collect()
+    }
+
+    fn executor_returning(triggers: Vec<TableTrigger>) -> MockTriggerSingleSourceQueryExecutor {
+        let mut executor = MockTriggerSingleSourceQueryExecutor::new();
+        executor
+            .expect_query_triggers()
+            .times(1)
+            .withf(|input| input.included_tables() == vec!["table1".to_string()])
+            .returning(move |_| triggers.clone());
+        executor
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_triggers_when_same_definitions() {
+        let trigger_differ = TriggerDiffer::new(
+            executor_returning(vec![TableTrigger::new("table1", "audit", AUDIT_TRIGGER)]),
+            executor_returning(vec![TableTrigger::new("table1", "audit", AUDIT_TRIGGER)]),
+        );
+
+        let diff_output = trigger_differ
+            .diff_all_triggers(&diff_payload(), "schema_name".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            trigger_diffs(diff_output),
+            vec![TriggerDiffOutput::NoDiff(
+                "table1".to_string(),
+                "audit".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_triggers_when_definition_changed() {
+        let changed_trigger = AUDIT_TRIGGER.replace("AFTER INSERT", "AFTER UPDATE");
+        let trigger_differ = TriggerDiffer::new(
+            executor_returning(vec![TableTrigger::new("table1", "audit", AUDIT_TRIGGER)]),
+            executor_returning(vec![TableTrigger::new(
+                "table1",
+                "audit",
+                changed_trigger.to_owned(),
+            )]),
+        );
+
+        let diff_output = trigger_differ
+            .diff_all_triggers(&diff_payload(), "schema_name".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            trigger_diffs(diff_output),
+            vec![TriggerDiffOutput::DefinitionChanged(
+                "table1".to_string(),
+                "audit".to_string(),
+                AUDIT_TRIGGER.to_string(),
+                changed_trigger
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_all_triggers_when_missing_in_either_database() {
+        let trigger_differ = TriggerDiffer::new(
+            executor_returning(vec![TableTrigger::new("table1", "audit", AUDIT_TRIGGER)]),
+            executor_returning(vec![TableTrigger::new(
+                "table1",
+                "touch",
+                "CREATE TRIGGER touch BEFORE UPDATE ON public.table1 FOR EACH ROW EXECUTE FUNCTION touch()",
+            )]),
+        );
+
+        let diff_output = trigger_differ
+            .diff_all_triggers(&diff_payload(), "schema_name".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            trigger_diffs(diff_output),
+            vec![
+                TriggerDiffOutput::NotExists(
+                    "table1".to_string(),
+                    "audit".to_string(),
+                    TableSource::Second(None)
+                ),
+                TriggerDiffOutput::NotExists(
+                    "table1".to_string(),
+                    "touch".to_string(),
+                    TableSource::First(None)
+                ),
+            ]
+        );
+    }
+}
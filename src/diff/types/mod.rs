@@ -1,6 +1,10 @@
+use std::fmt::{Display, Formatter};
+
+use deadpool_postgres::tokio_postgres::error::SqlState;
+
 use crate::diff::diff_output::DiffOutput;
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct SchemaName(String);
 
 impl SchemaName {
@@ -14,5 +18,99 @@ impl SchemaName {
 }
 
 pub trait DiffOutputMarker {
-    fn convert(self) -> DiffOutput;
+    fn convert(self, schema_name: SchemaName) -> DiffOutput;
 }
+
+/// The error reported when a query is cancelled for exceeding the statement timeout.
+#[derive(Debug)]
+pub struct QueryTimeoutError;
+
+impl QueryTimeoutError {
+    /// Maps a database error to an [`anyhow::Error`], preserving statement timeouts
+    /// as a [`QueryTimeoutError`] and missing relations as a [`RelationNotFoundError`].
+    /// Any other error is reported with the given message, followed by the database error.
+    pub fn map_db_error(
+        error: &deadpool_postgres::tokio_postgres::Error,
+        message: &str,
+    ) -> anyhow::Error {
+        match error.code() {
+            Some(code) if code == &SqlState::QUERY_CANCELED => {
+                anyhow::Error::new(QueryTimeoutError)
+            }
+            Some(code) if code == &SqlState::UNDEFINED_TABLE => {
+                anyhow::Error::new(RelationNotFoundError)
+            }
+            _ => anyhow::anyhow!("{}: {}", message, error),
+        }
+    }
+
+    /// Maps the error of a query to an [`anyhow::Error`], as [`QueryTimeoutError::map_db_error`]
+    /// does when the database raised it. Any other error is reported with the given message.
+    pub fn map_error(error: anyhow::Error, message: &str) -> anyhow::Error {
+        match error.downcast_ref::<deadpool_postgres::tokio_postgres::Error>() {
+            Some(db_error) => Self::map_db_error(db_error, message),
+            None => anyhow::anyhow!("{}: {}", message, error),
+        }
+    }
+
+    /// Determines whether the error is a [`QueryTimeoutError`].
+    pub fn is_timeout(error: &anyhow::Error) -> bool {
+        error.downcast_ref::<QueryTimeoutError>().is_some()
+    }
+}
+
+impl Display for QueryTimeoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Query exceeded the statement timeout")
+    }
+}
+
+impl std::error::Error for QueryTimeoutError {}
+
+/// The error reported when a query targets a relation that does not exist.
+#[derive(Debug)]
+pub struct RelationNotFoundError;
+
+impl RelationNotFoundError {
+    /// Determines whether the error is a [`RelationNotFoundError`].
+    pub fn is_not_found(error: &anyhow::Error) -> bool {
+        error.downcast_ref::<RelationNotFoundError>().is_some()
+    }
+}
+
+impl Display for RelationNotFoundError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Relation does not exist")
+    }
+}
+
+impl std::error::Error for RelationNotFoundError {}
+
+/// The error reported when a hash query fails, carrying the query so that it can be run by hand.
+#[derive(Debug)]
+pub struct HashQueryError {
+    message: String,
+    query: String,
+}
+
+impl HashQueryError {
+    pub fn new(message: impl Into<String>, query: &str) -> Self {
+        Self {
+            message: message.into(),
+            // Keep the query on a single line, so that it fits in a single line of any report
+            query: query.split_whitespace().collect::<Vec<&str>>().join(" "),
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+}
+
+impl Display for HashQueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (query: {})", self.message, self.query)
+    }
+}
+
+impl std::error::Error for HashQueryError {}